@@ -0,0 +1,106 @@
+#[cfg(not(target_arch = "wasm32"))]
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+#[cfg(not(target_arch = "wasm32"))]
+use ratatui::{Terminal, backend::TestBackend};
+#[cfg(not(target_arch = "wasm32"))]
+use std::hint::black_box;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use tuitype::TypingSession;
+#[cfg(not(target_arch = "wasm32"))]
+use tuitype::clock::ManualClock;
+#[cfg(not(target_arch = "wasm32"))]
+use tuitype::config::{Config, TestMode};
+#[cfg(not(target_arch = "wasm32"))]
+use tuitype::ui::{App, render};
+
+#[cfg(not(target_arch = "wasm32"))]
+const TEXT_LENGTHS: [usize; 3] = [1_000, 10_000, 100_000];
+
+#[cfg(not(target_arch = "wasm32"))]
+fn config(length: usize) -> Config {
+    let text: String = "lorem ipsum dolor sit amet "
+        .chars()
+        .cycle()
+        .take(length)
+        .collect();
+
+    Config {
+        test_mode: TestMode::Custom,
+        custom_text: Some(text),
+        seed: Some(42),
+        ..Config::default()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn typed_session(length: usize) -> (TypingSession, Arc<ManualClock>) {
+    let config = config(length);
+    let clock = Arc::new(ManualClock::new());
+    let mut session = TypingSession::with_clock(&config, clock.clone());
+
+    let text: Vec<char> = session.text_source.chars().to_vec();
+    for &c in &text[..length - 10] {
+        clock.advance(Duration::from_millis(50));
+        session.type_char(c);
+    }
+
+    (session, clock)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn keystroke(c: &mut Criterion) {
+    let mut group = c.benchmark_group("keystroke");
+
+    for length in TEXT_LENGTHS {
+        group.bench_with_input(
+            BenchmarkId::new("type_char", length),
+            &length,
+            |b, &length| {
+                let (mut session, clock) = typed_session(length);
+                let next = session.text_source.char_at(session.cursor_pos).unwrap();
+
+                b.iter(|| {
+                    clock.advance(Duration::from_millis(1));
+                    session.type_char(black_box(next));
+                    session.backspace();
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn render_frame(c: &mut Criterion) {
+    let mut group = c.benchmark_group("render");
+
+    for length in TEXT_LENGTHS {
+        group.bench_with_input(BenchmarkId::new("typing", length), &length, |b, &length| {
+            let clock = Arc::new(ManualClock::new());
+            let mut app = App::with_clock(config(length), clock.clone());
+            app.session = typed_session(length).0;
+
+            let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+            b.iter(|| {
+                terminal
+                    .draw(|frame| render(black_box(&app), frame).unwrap())
+                    .unwrap();
+            });
+        });
+    }
+
+    group.finish();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+criterion_group!(benches, keystroke, render_frame);
+#[cfg(not(target_arch = "wasm32"))]
+criterion_main!(benches);
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -0,0 +1,171 @@
+use crate::VERSION;
+use crate::net::DEFAULT_PORT;
+use crate::profile::validate_name;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::storage::{FileStorage, ReadOnlyStorage, SharedStorage};
+use anyhow::{Result, anyhow, bail};
+use std::path::PathBuf;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+
+pub const USAGE: &str = "\
+Usage: tuitype [OPTIONS]
+
+Options:
+      --script <FILE>        Replay timed keystrokes from FILE instead of the keyboard
+      --exit-after-script    Quit once every scripted keystroke has been sent
+      --seed <N>             Generate the same words and quotes as anyone else using seed N
+      --host <PORT>          Host a multiplayer race on the local network
+      --join <ADDR>          Join a race hosted at ADDR (host or host:port)
+      --name <NAME>          Name shown to other racers (defaults to your user name)
+      --profile <NAME>       Use the settings and history of profile NAME, creating it if needed
+      --config <DIR>         Keep settings and history in DIR instead of the default config directory
+      --read-only            Never write settings, history or profiles back to disk
+  -h, --help                 Print help
+  -V, --version              Print version
+
+Script files contain one keystroke per line, e.g. `t=120ms key=a`.
+Keys are single characters or space, enter, esc, tab, backspace, up, down,
+left, right, optionally prefixed with ctrl+, alt+ or shift+. Lines starting
+with # are ignored.
+
+The config directory can also be set with the TUITYPE_CONFIG_DIR environment
+variable. Otherwise $XDG_CONFIG_HOME/tuitype is used when XDG_CONFIG_HOME is set.";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliArgs {
+    pub script: Option<PathBuf>,
+
+    pub exit_after_script: bool,
+
+    pub seed: Option<u64>,
+
+    pub race: Option<RaceMode>,
+
+    pub name: Option<String>,
+
+    pub profile: Option<String>,
+
+    pub config_dir: Option<PathBuf>,
+
+    pub read_only: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RaceMode {
+    Host(u16),
+
+    Join(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    Run(CliArgs),
+
+    Help,
+
+    Version,
+}
+
+impl CliCommand {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self> {
+        let mut parsed = CliArgs::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(Self::Help),
+                "-V" | "--version" => return Ok(Self::Version),
+                "--script" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--script requires a file path"))?;
+                    parsed.script = Some(PathBuf::from(path));
+                }
+                "--exit-after-script" => parsed.exit_after_script = true,
+                "--seed" => {
+                    let seed = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--seed requires a number"))?;
+                    parsed.seed = Some(
+                        seed.parse()
+                            .map_err(|_| anyhow!("invalid seed '{seed}', expected a number"))?,
+                    );
+                }
+                "--host" => {
+                    let port = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--host requires a port"))?;
+                    parsed.race =
+                        Some(RaceMode::Host(port.parse().map_err(|_| {
+                            anyhow!("invalid port '{port}', expected a number")
+                        })?));
+                }
+                "--join" => {
+                    let addr = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--join requires an address"))?;
+                    parsed.race = Some(RaceMode::Join(if addr.contains(':') {
+                        addr
+                    } else {
+                        format!("{addr}:{DEFAULT_PORT}")
+                    }));
+                }
+                "--name" => {
+                    parsed.name = Some(
+                        args.next()
+                            .ok_or_else(|| anyhow!("--name requires a name"))?,
+                    );
+                }
+                "--profile" => {
+                    let name = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--profile requires a name"))?;
+                    parsed.profile = Some(validate_name(&name)?);
+                }
+                "--config" => {
+                    let dir = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--config requires a directory"))?;
+                    parsed.config_dir = Some(PathBuf::from(dir));
+                }
+                "--read-only" => parsed.read_only = true,
+                _ => bail!("unknown argument '{arg}'\n\n{USAGE}"),
+            }
+        }
+
+        if parsed.exit_after_script && parsed.script.is_none() {
+            bail!("--exit-after-script requires --script");
+        }
+
+        Ok(Self::Run(parsed))
+    }
+}
+
+impl CliArgs {
+    pub fn player_name(&self) -> String {
+        self.name
+            .clone()
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| "player".to_string())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn storage(&self) -> SharedStorage {
+        let storage = match &self.config_dir {
+            Some(dir) => FileStorage::new(dir),
+            None => FileStorage::config_dir(),
+        };
+        if self.read_only {
+            Arc::new(ReadOnlyStorage::new(Arc::new(storage)))
+        } else {
+            Arc::new(storage)
+        }
+    }
+}
+
+pub fn version_string() -> String {
+    format!("tuitype {VERSION}")
+}
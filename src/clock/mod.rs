@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Duration;
+
+    fn wall_time(&self) -> DateTime<Utc>;
+}
+
+pub type SharedClock = Arc<dyn Clock>;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub struct SystemClock {
+    origin: std::time::Instant,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SystemClock {
+    pub fn new() -> Self {
+        Self {
+            origin: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+
+    fn wall_time(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[derive(Default)]
+pub struct PerformanceClock;
+
+#[cfg(target_arch = "wasm32")]
+impl Clock for PerformanceClock {
+    fn now(&self) -> Duration {
+        let millis = web_sys::window()
+            .and_then(|window| window.performance())
+            .map(|performance| performance.now())
+            .unwrap_or_else(js_sys::Date::now);
+        Duration::from_secs_f64(millis.max(0.0) / 1000.0)
+    }
+
+    fn wall_time(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    micros: AtomicU64,
+
+    wall_origin: DateTime<Utc>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn starting_at(wall_origin: DateTime<Utc>) -> Self {
+        Self {
+            micros: AtomicU64::default(),
+            wall_origin,
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.micros
+            .fetch_add(by.as_micros() as u64, Ordering::SeqCst);
+    }
+
+    pub fn set(&self, to: Duration) {
+        self.micros.store(to.as_micros() as u64, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        Duration::from_micros(self.micros.load(Ordering::SeqCst))
+    }
+
+    fn wall_time(&self) -> DateTime<Utc> {
+        self.wall_origin + self.now()
+    }
+}
+
+pub fn system_clock() -> SharedClock {
+    #[cfg(target_arch = "wasm32")]
+    {
+        Arc::new(PerformanceClock)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Arc::new(SystemClock::new())
+    }
+}
@@ -0,0 +1,191 @@
+use super::Config;
+use crate::storage::Storage;
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::fmt::Write;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+struct Setting {
+    key: &'static str,
+    doc: &'static str,
+    example: &'static str,
+}
+
+const HEADER: &str = "\
+# TuiType settings.
+#
+# Every setting is optional, and a missing setting keeps its default.
+# TuiType rewrites this file when you change settings in the app.
+# A .tuitype.toml in a project directory, or any directory above it,
+# overrides these settings while you run TuiType from there.
+";
+
+const SETTINGS: [Setting; 18] = [
+    Setting {
+        key: "version",
+        doc: "Config format version. Older files are migrated automatically.",
+        example: "1",
+    },
+    Setting {
+        key: "test_mode",
+        doc: "{ Words = 25 }, { Timed = 30 }, \"Quote\" or \"Custom\".",
+        example: "{ Words = 25 }",
+    },
+    Setting {
+        key: "difficulty",
+        doc: "Word list for word and timed tests: \"Easy\", \"Medium\" or \"Hard\".",
+        example: "\"Medium\"",
+    },
+    Setting {
+        key: "word_list",
+        doc: "Words to use instead of the difficulty's word list.",
+        example: "[\"borrow\", \"lifetime\", \"trait\"]",
+    },
+    Setting {
+        key: "custom_text",
+        doc: "Text to type in \"Custom\" mode.",
+        example: "\"The quick brown fox jumps over the lazy dog.\"",
+    },
+    Setting {
+        key: "theme_type",
+        doc: "\"Light\", \"Dark\", \"Sepia\", \"Matrix\" or \"Ocean\".",
+        example: "\"Dark\"",
+    },
+    Setting {
+        key: "font_style",
+        doc: "\"Default\", \"Simple\", \"Monospace\" or \"Large\".",
+        example: "\"Default\"",
+    },
+    Setting {
+        key: "repeat_test",
+        doc: "Type the same text again on every restart.",
+        example: "false",
+    },
+    Setting {
+        key: "last_test_text",
+        doc: "The text repeated by repeat_test. TuiType fills this in.",
+        example: "\"\"",
+    },
+    Setting {
+        key: "end_on_first_error",
+        doc: "End the test as soon as you make a mistake.",
+        example: "false",
+    },
+    Setting {
+        key: "caret_style",
+        doc: "\"Block\", \"Underline\", \"Bar\" or \"Off\".",
+        example: "\"Block\"",
+    },
+    Setting {
+        key: "use_terminal_cursor",
+        doc: "Use the terminal's own cursor as the caret.",
+        example: "false",
+    },
+    Setting {
+        key: "pace_caret",
+        doc: "\"Off\", \"Fixed\", \"PersonalBest\" or \"Average\".",
+        example: "\"Off\"",
+    },
+    Setting {
+        key: "pace_caret_wpm",
+        doc: "Speed of the \"Fixed\" pace caret.",
+        example: "60",
+    },
+    Setting {
+        key: "seed",
+        doc: "Generate the same text as anyone else using this seed.",
+        example: "20240501",
+    },
+    Setting {
+        key: "key_debounce",
+        doc: "\"PressRelease\", \"FixedWindow\" or \"Off\".",
+        example: "\"PressRelease\"",
+    },
+    Setting {
+        key: "debounce_ms",
+        doc: "Repeat presses within this many milliseconds are dropped by \"FixedWindow\".",
+        example: "60",
+    },
+    Setting {
+        key: "backspace_mode",
+        doc: "\"Normal\", \"Confidence\" or \"Disabled\".",
+        example: "\"Normal\"",
+    },
+];
+
+const KEYBINDINGS_DOC: &str = "\
+# Keys for each action, e.g. \"tab\", \"esc\", \"ctrl+p\" or \"r\".
+# repeat_text and next_test only apply on the results screen.
+";
+
+impl ConfigFormat {
+    pub fn storage_key(self) -> &'static str {
+        match self {
+            Self::Toml => Config::STORAGE_KEY,
+            Self::Json => Config::JSON_STORAGE_KEY,
+        }
+    }
+
+    pub fn detect(storage: &dyn Storage) -> Result<Option<(Self, String)>> {
+        for format in [Self::Toml, Self::Json] {
+            if let Some(contents) = storage.read(format.storage_key())? {
+                return Ok(Some((format, contents)));
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn parse(self, contents: &str) -> Result<Value> {
+        match self {
+            Self::Json => Ok(serde_json::from_str(contents)?),
+            Self::Toml => {
+                let table: toml::Table = toml::from_str(contents).map_err(|err| {
+                    let line = err
+                        .span()
+                        .map_or(1, |span| contents[..span.start].lines().count().max(1));
+                    anyhow!("line {line}: {}", err.message())
+                })?;
+                Ok(serde_json::to_value(table)?)
+            }
+        }
+    }
+
+    pub fn render(self, config: &Config) -> Result<String> {
+        match self {
+            Self::Json => Ok(serde_json::to_string_pretty(config)?),
+            Self::Toml => render_documented_toml(config),
+        }
+    }
+}
+
+fn render_documented_toml(config: &Config) -> Result<String> {
+    let mut table = toml::Table::try_from(config)?;
+    let keybindings = table.remove("keybindings");
+    let mut output = HEADER.to_string();
+
+    for setting in &SETTINGS {
+        writeln!(output, "\n# {}", setting.doc)?;
+        match table.remove(setting.key) {
+            Some(value) => writeln!(output, "{} = {value}", setting.key)?,
+            None => writeln!(output, "# {} = {}", setting.key, setting.example)?,
+        }
+    }
+
+    for (key, value) in table {
+        writeln!(output, "\n{key} = {value}")?;
+    }
+
+    if let Some(toml::Value::Table(bindings)) = keybindings {
+        write!(output, "\n{KEYBINDINGS_DOC}[keybindings]\n")?;
+        for (action, key) in bindings {
+            writeln!(output, "{action} = {key}")?;
+        }
+    }
+
+    Ok(output)
+}
@@ -0,0 +1,232 @@
+use crate::input::keys::{KeyCode, KeyEvent, KeyModifiers};
+use crate::input::parse_key;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyChord {
+    pub code: KeyCode,
+
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    pub const fn key(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    pub const fn ctrl(c: char) -> Self {
+        Self::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        *self == Self::from(*event)
+    }
+
+    fn normalized(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+
+        let code = match code {
+            KeyCode::Char(c) if c.is_uppercase() => {
+                modifiers |= KeyModifiers::SHIFT;
+                KeyCode::Char(c.to_lowercase().next().unwrap_or(c))
+            }
+            KeyCode::Char(c) => {
+                if !c.is_alphabetic() {
+                    modifiers.remove(KeyModifiers::SHIFT);
+                }
+                KeyCode::Char(c)
+            }
+            KeyCode::BackTab => {
+                modifiers |= KeyModifiers::SHIFT;
+                KeyCode::BackTab
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            code => code,
+        };
+
+        Self { code, modifiers }
+    }
+
+    pub fn types_text(&self) -> bool {
+        let modified = self
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        !modified && matches!(self.code, KeyCode::Char(_) | KeyCode::Backspace)
+    }
+
+    fn key_name(&self) -> String {
+        match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_uppercase().to_string(),
+            KeyCode::Tab | KeyCode::BackTab => "Tab".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Backspace => "Backspace".to_string(),
+            KeyCode::Delete => "Delete".to_string(),
+            KeyCode::Insert => "Insert".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PageUp".to_string(),
+            KeyCode::PageDown => "PageDown".to_string(),
+            KeyCode::Up => "Up".to_string(),
+            KeyCode::Down => "Down".to_string(),
+            KeyCode::Left => "Left".to_string(),
+            KeyCode::Right => "Right".to_string(),
+            KeyCode::F(n) => format!("F{n}"),
+            code => format!("{code:?}"),
+        }
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(event: KeyEvent) -> Self {
+        Self::normalized(event.code, event.modifiers)
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse_key(s)
+            .map(Self::from)
+            .with_context(|| format!("invalid key binding '{s}'"))
+    }
+}
+
+impl TryFrom<String> for KeyChord {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<KeyChord> for String {
+    fn from(chord: KeyChord) -> Self {
+        let mut parts = Vec::new();
+        if chord.modifiers.contains(KeyModifiers::CONTROL) {
+            parts.push("ctrl".to_string());
+        }
+        if chord.modifiers.contains(KeyModifiers::ALT) {
+            parts.push("alt".to_string());
+        }
+        if chord.modifiers.contains(KeyModifiers::SHIFT) {
+            parts.push("shift".to_string());
+        }
+        parts.push(chord.key_name().to_lowercase());
+        parts.join("+")
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", self.key_name())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Keybindings {
+    pub restart: KeyChord,
+
+    pub open_menu: KeyChord,
+
+    pub quit: KeyChord,
+
+    pub repeat_text: KeyChord,
+
+    pub next_test: KeyChord,
+
+    pub toggle_stats: KeyChord,
+
+    pub command_palette: KeyChord,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self {
+            restart: KeyChord::key(KeyCode::Tab),
+            open_menu: KeyChord::key(KeyCode::Esc),
+            quit: KeyChord::ctrl('c'),
+            repeat_text: KeyChord::key(KeyCode::Char('r')),
+            next_test: KeyChord::key(KeyCode::Enter),
+            toggle_stats: KeyChord::ctrl('s'),
+            command_palette: KeyChord::ctrl('p'),
+        }
+    }
+}
+
+impl Keybindings {
+    const RESULTS_ONLY: [&str; 2] = ["repeat_text", "next_test"];
+
+    fn bindings(&self) -> [(&'static str, KeyChord); 7] {
+        [
+            ("restart", self.restart),
+            ("open_menu", self.open_menu),
+            ("quit", self.quit),
+            ("toggle_stats", self.toggle_stats),
+            ("command_palette", self.command_palette),
+            ("repeat_text", self.repeat_text),
+            ("next_test", self.next_test),
+        ]
+    }
+
+    fn chord_mut(&mut self, action: &str) -> Option<&mut KeyChord> {
+        match action {
+            "restart" => Some(&mut self.restart),
+            "open_menu" => Some(&mut self.open_menu),
+            "quit" => Some(&mut self.quit),
+            "toggle_stats" => Some(&mut self.toggle_stats),
+            "command_palette" => Some(&mut self.command_palette),
+            "repeat_text" => Some(&mut self.repeat_text),
+            "next_test" => Some(&mut self.next_test),
+            _ => None,
+        }
+    }
+
+    pub fn resolve_conflicts(&mut self, fallback: &Self) -> Vec<String> {
+        let mut rejected = Vec::new();
+
+        for (action, previous) in fallback.bindings() {
+            let bindings = self.bindings();
+            let Some(&(_, chord)) = bindings.iter().find(|(name, _)| *name == action) else {
+                continue;
+            };
+            if chord == previous {
+                continue;
+            }
+
+            let clashes = bindings
+                .iter()
+                .any(|&(other, other_chord)| other != action && other_chord == chord);
+            let blocks_typing = !Self::RESULTS_ONLY.contains(&action) && chord.types_text();
+            if (clashes || blocks_typing)
+                && let Some(binding) = self.chord_mut(action)
+            {
+                *binding = previous;
+                rejected.push(format!("keybindings.{action}"));
+            }
+        }
+
+        rejected
+    }
+}
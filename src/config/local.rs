@@ -0,0 +1,136 @@
+use super::{Config, ConfigFormat};
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+pub const LOCAL_CONFIG_FILE: &str = ".tuitype.toml";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalConfig {
+    pub path: PathBuf,
+
+    settings: Map<String, Value>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalOverrides {
+    local: LocalConfig,
+
+    keys: Vec<String>,
+
+    user: Config,
+
+    applied: Config,
+}
+
+impl LocalConfig {
+    pub fn find(start: &Path) -> Option<PathBuf> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(LOCAL_CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+
+    pub fn discover() -> Result<Option<Self>> {
+        let cwd = std::env::current_dir()?;
+        Self::find(&cwd).map(Self::load).transpose()
+    }
+
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("could not read {}", path.display()))?;
+        let Value::Object(mut settings) = ConfigFormat::Toml
+            .parse(&contents)
+            .with_context(|| format!("could not parse {}", path.display()))?
+        else {
+            bail!("{} is not a settings table", path.display());
+        };
+        settings.remove("version");
+
+        let dir = path.parent().unwrap_or(Path::new("."));
+        if let Some(file) = settings.remove("custom_text_file") {
+            let words = read_words(dir, &file, "custom_text_file")?;
+            settings.insert("custom_text".to_string(), words.join(" ").into());
+        }
+        if let Some(file) = settings.remove("word_list_file") {
+            let words = read_words(dir, &file, "word_list_file")?;
+            settings.insert("word_list".to_string(), words.into());
+        }
+
+        Ok(Self { path, settings })
+    }
+
+    pub fn apply(self, user: &Config) -> (LocalOverrides, Vec<String>) {
+        let known = serde_json::to_value(Config::default()).unwrap_or_default();
+        let (known, unknown): (Map<String, Value>, Map<String, Value>) = self
+            .settings
+            .clone()
+            .into_iter()
+            .partition(|(key, _)| known.get(key).is_some());
+
+        let keys = known.keys().cloned().collect();
+        let (applied, mut rejected) = user.with_overrides(known);
+        rejected.extend(unknown.into_iter().map(|(key, _)| key));
+
+        let overrides = LocalOverrides {
+            local: self,
+            keys,
+            user: user.clone(),
+            applied,
+        };
+        (overrides, rejected)
+    }
+}
+
+impl LocalOverrides {
+    pub fn path(&self) -> &Path {
+        &self.local.path
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.applied
+    }
+
+    pub fn into_local(self) -> LocalConfig {
+        self.local
+    }
+
+    pub fn user_config(&self, current: &Config) -> Result<Config> {
+        let mut current = serde_json::to_value(current)?;
+        let user = serde_json::to_value(&self.user)?;
+        let applied = serde_json::to_value(&self.applied)?;
+
+        for key in &self.keys {
+            if current[key] == applied[key] {
+                current[key] = user[key].clone();
+            }
+        }
+
+        Ok(serde_json::from_value(current)?)
+    }
+}
+
+fn read_words(dir: &Path, file: &Value, setting: &str) -> Result<Vec<String>> {
+    let file = file
+        .as_str()
+        .ok_or_else(|| anyhow!("{setting} must be a file path"))?;
+    let path = dir
+        .join(file)
+        .canonicalize()
+        .with_context(|| format!("could not read {setting} {file}"))?;
+    let root = dir
+        .canonicalize()
+        .with_context(|| format!("could not read {}", dir.display()))?;
+    if !path.starts_with(&root) {
+        bail!("{setting} {file} is outside {}", root.display());
+    }
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("could not read {setting} {}", path.display()))?;
+
+    let words: Vec<String> = text.split_whitespace().map(str::to_string).collect();
+    if words.is_empty() {
+        bail!("{setting} {} is empty", path.display());
+    }
+    Ok(words)
+}
@@ -0,0 +1,28 @@
+use serde_json::{Map, Value};
+
+pub const CONFIG_VERSION: u32 = 1;
+
+pub type Migration = fn(&mut Map<String, Value>);
+
+const MIGRATIONS: [Migration; CONFIG_VERSION as usize] = [unversioned_to_v1];
+
+pub fn stored_version(config: &Map<String, Value>) -> u32 {
+    config
+        .get("version")
+        .and_then(Value::as_u64)
+        .map_or(0, |version| version.min(u32::MAX as u64) as u32)
+}
+
+pub fn migrate(config: &mut Map<String, Value>) {
+    migrate_with(config, &MIGRATIONS);
+}
+
+pub fn migrate_with(config: &mut Map<String, Value>, migrations: &[Migration]) {
+    let from = stored_version(config) as usize;
+    for migration in migrations.iter().skip(from) {
+        migration(config);
+    }
+    config.insert("version".to_string(), migrations.len().into());
+}
+
+fn unversioned_to_v1(_config: &mut Map<String, Value>) {}
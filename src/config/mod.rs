@@ -1,8 +1,17 @@
+mod format;
+mod keybindings;
+mod local;
+mod migrate;
+
+use crate::storage::Storage;
 use crate::ui::ThemeType;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::PathBuf;
+
+pub use format::ConfigFormat;
+pub use keybindings::{KeyChord, Keybindings};
+pub use local::{LOCAL_CONFIG_FILE, LocalConfig, LocalOverrides};
+pub use migrate::{CONFIG_VERSION, Migration, migrate_with};
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Difficulty {
@@ -23,11 +32,99 @@ pub enum TestMode {
     Custom,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum FontStyle {
     Default,
     Simple,
     Monospace,
+    Large,
+}
+
+impl FontStyle {
+    pub fn next(self) -> Self {
+        match self {
+            FontStyle::Default => FontStyle::Simple,
+            FontStyle::Simple => FontStyle::Monospace,
+            FontStyle::Monospace => FontStyle::Large,
+            FontStyle::Large => FontStyle::Default,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum CaretStyle {
+    #[default]
+    Block,
+    Underline,
+    Bar,
+    Off,
+}
+
+impl CaretStyle {
+    pub fn next(self) -> Self {
+        match self {
+            CaretStyle::Block => CaretStyle::Underline,
+            CaretStyle::Underline => CaretStyle::Bar,
+            CaretStyle::Bar => CaretStyle::Off,
+            CaretStyle::Off => CaretStyle::Block,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum PaceCaret {
+    #[default]
+    Off,
+    Fixed,
+    PersonalBest,
+    Average,
+}
+
+impl PaceCaret {
+    pub fn next(self) -> Self {
+        match self {
+            PaceCaret::Off => PaceCaret::Fixed,
+            PaceCaret::Fixed => PaceCaret::PersonalBest,
+            PaceCaret::PersonalBest => PaceCaret::Average,
+            PaceCaret::Average => PaceCaret::Off,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum KeyDebounce {
+    Off,
+    #[default]
+    PressRelease,
+    FixedWindow,
+}
+
+impl KeyDebounce {
+    pub fn next(self) -> Self {
+        match self {
+            KeyDebounce::Off => KeyDebounce::PressRelease,
+            KeyDebounce::PressRelease => KeyDebounce::FixedWindow,
+            KeyDebounce::FixedWindow => KeyDebounce::Off,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum BackspaceMode {
+    #[default]
+    Normal,
+    Confidence,
+    Disabled,
+}
+
+impl BackspaceMode {
+    pub fn next(self) -> Self {
+        match self {
+            BackspaceMode::Normal => BackspaceMode::Confidence,
+            BackspaceMode::Confidence => BackspaceMode::Disabled,
+            BackspaceMode::Disabled => BackspaceMode::Normal,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -48,11 +145,16 @@ pub struct ThemeConfig {
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
+    pub version: u32,
+
     pub test_mode: TestMode,
 
     pub difficulty: Difficulty,
 
+    pub word_list: Option<Vec<String>>,
+
     pub custom_text: Option<String>,
 
     pub theme_type: ThemeType,
@@ -64,57 +166,202 @@ pub struct Config {
     pub last_test_text: Option<String>,
 
     pub end_on_first_error: bool,
+
+    pub caret_style: CaretStyle,
+
+    pub use_terminal_cursor: bool,
+
+    pub pace_caret: PaceCaret,
+
+    pub pace_caret_wpm: u32,
+
+    pub seed: Option<u64>,
+
+    pub key_debounce: KeyDebounce,
+
+    pub debounce_ms: u64,
+
+    pub backspace_mode: BackspaceMode,
+
+    pub keybindings: Keybindings,
+}
+
+fn default_pace_caret_wpm() -> u32 {
+    60
+}
+
+fn default_debounce_ms() -> u64 {
+    60
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
+            version: CONFIG_VERSION,
             test_mode: TestMode::Words(10),
             difficulty: Difficulty::Medium,
+            word_list: None,
             custom_text: None,
             theme_type: ThemeType::Dark,
             font_style: FontStyle::Default,
             repeat_test: false,
             last_test_text: None,
             end_on_first_error: false,
+            caret_style: CaretStyle::Block,
+            use_terminal_cursor: false,
+            pace_caret: PaceCaret::Off,
+            pace_caret_wpm: default_pace_caret_wpm(),
+            seed: None,
+            key_debounce: KeyDebounce::default(),
+            debounce_ms: default_debounce_ms(),
+            backspace_mode: BackspaceMode::default(),
+            keybindings: Keybindings::default(),
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadedConfig {
+    pub config: Config,
+
+    pub warnings: Vec<String>,
+}
+
 impl Config {
-    fn get_config_dir() -> Result<PathBuf> {
-        let mut dir = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
-        dir.push("tuitype");
-        if !dir.exists() {
-            fs::create_dir_all(&dir)?;
-        }
-        Ok(dir)
-    }
+    pub const STORAGE_KEY: &str = "config.toml";
+
+    pub const JSON_STORAGE_KEY: &str = "config.json";
 
-    fn get_config_path() -> Result<PathBuf> {
-        let mut path = Self::get_config_dir()?;
-        path.push("config.json");
-        Ok(path)
+    const NESTED_SETTINGS: [&str; 1] = ["keybindings"];
+
+    pub fn save_to(&self, storage: &dyn Storage) -> Result<()> {
+        let format = match storage.read(Self::STORAGE_KEY)? {
+            None if storage.read(Self::JSON_STORAGE_KEY)?.is_some() => ConfigFormat::Json,
+            _ => ConfigFormat::Toml,
+        };
+        storage.write(format.storage_key(), &format.render(self)?)
     }
 
-    pub fn save(&self) -> Result<()> {
-        let path = Self::get_config_path()?;
-        let serialized = serde_json::to_string_pretty(self)?;
-        fs::write(path, serialized)?;
-        Ok(())
+    pub fn load_from(storage: &dyn Storage) -> LoadedConfig {
+        let mut warnings = Vec::new();
+
+        let (format, contents) = match ConfigFormat::detect(storage) {
+            Ok(Some(found)) => found,
+            Ok(None) => {
+                let config = Self::default();
+                config.save_to(storage).ok();
+                return LoadedConfig { config, warnings };
+            }
+            Err(err) => {
+                warnings.push(format!(
+                    "Could not read your settings: {err:#}. Default settings are in use."
+                ));
+                return LoadedConfig {
+                    config: Self::default(),
+                    warnings,
+                };
+            }
+        };
+
+        let key = format.storage_key();
+        let mut newer_version = None;
+        let (config, lossy) = match format.parse(&contents) {
+            Ok(serde_json::Value::Object(mut stored)) => {
+                let version = migrate::stored_version(&stored);
+                if version > CONFIG_VERSION {
+                    warnings.push(format!(
+                        "{key} was written by a newer version of TuiType (config version {version}). \
+                         Settings this version doesn't know about were dropped."
+                    ));
+                    newer_version = Some(version);
+                }
+                migrate::migrate(&mut stored);
+
+                let (config, rejected) = Self::default().with_overrides(stored);
+                if !rejected.is_empty() {
+                    warnings.push(format!(
+                        "Ignored invalid settings: {}. Their defaults are in use.",
+                        rejected.join(", ")
+                    ));
+                }
+                (config, version > CONFIG_VERSION || !rejected.is_empty())
+            }
+            Ok(_) => {
+                warnings.push(format!(
+                    "{key} is not a settings object. Default settings are in use."
+                ));
+                (Self::default(), true)
+            }
+            Err(err) => {
+                warnings.push(format!(
+                    "{key} could not be parsed ({err:#}). Default settings are in use."
+                ));
+                (Self::default(), true)
+            }
+        };
+
+        if lossy {
+            let backup = match newer_version {
+                Some(version) => format!("{key}.v{version}.bak"),
+                None => format!("{key}.{}.bak", chrono::Local::now().format("%Y%m%d-%H%M%S")),
+            };
+            match storage.write(&backup, &contents) {
+                Ok(()) => {
+                    warnings.push(format!("The original file was saved as {backup}."));
+                    if newer_version.is_none()
+                        && let Err(err) = config.save_to(storage)
+                    {
+                        warnings.push(format!("Could not save the repaired settings: {err:#}"));
+                    }
+                }
+                Err(err) => warnings.push(format!("Could not back up the original file: {err:#}")),
+            }
+        }
+
+        LoadedConfig { config, warnings }
     }
 
-    pub fn load() -> Result<Self> {
-        let path = Self::get_config_path()?;
-        if path.exists() {
-            let contents = fs::read_to_string(path)?;
-            let config = serde_json::from_str(&contents)?;
-            Ok(config)
-        } else {
-            let config = Self::default();
-            config.save()?;
-            Ok(config)
+    pub fn with_overrides(
+        &self,
+        stored: serde_json::Map<String, serde_json::Value>,
+    ) -> (Self, Vec<String>) {
+        let mut accepted = serde_json::to_value(self).unwrap_or_default();
+        let mut rejected = Vec::new();
+
+        for (key, value) in stored {
+            match value {
+                serde_json::Value::Object(fields)
+                    if Self::NESTED_SETTINGS.contains(&key.as_str()) =>
+                {
+                    for (field, value) in fields {
+                        let mut candidate = accepted.clone();
+                        candidate[&key][&field] = value;
+                        if Self::accepts(&candidate) {
+                            accepted = candidate;
+                        } else {
+                            rejected.push(format!("{key}.{field}"));
+                        }
+                    }
+                }
+                value => {
+                    let mut candidate = accepted.clone();
+                    candidate[&key] = value;
+                    if Self::accepts(&candidate) {
+                        accepted = candidate;
+                    } else {
+                        rejected.push(key);
+                    }
+                }
+            }
         }
+
+        let mut config: Self = serde_json::from_value(accepted).unwrap_or_else(|_| self.clone());
+        rejected.extend(config.keybindings.resolve_conflicts(&self.keybindings));
+        (config, rejected)
+    }
+
+    fn accepts(candidate: &serde_json::Value) -> bool {
+        Self::deserialize(candidate).is_ok()
     }
 }
 
@@ -128,6 +375,49 @@ pub fn theme_name(theme_type: ThemeType) -> &'static str {
     }
 }
 
+pub fn font_style_name(style: FontStyle) -> &'static str {
+    match style {
+        FontStyle::Default => "Default",
+        FontStyle::Simple => "Simple",
+        FontStyle::Monospace => "Monospace",
+        FontStyle::Large => "Large",
+    }
+}
+
+pub fn caret_style_name(style: CaretStyle) -> &'static str {
+    match style {
+        CaretStyle::Block => "Block",
+        CaretStyle::Underline => "Underline",
+        CaretStyle::Bar => "Bar",
+        CaretStyle::Off => "Off",
+    }
+}
+
+pub fn key_debounce_name(debounce: KeyDebounce, window_ms: u64) -> String {
+    match debounce {
+        KeyDebounce::Off => "Off".to_string(),
+        KeyDebounce::PressRelease => "Press/Release".to_string(),
+        KeyDebounce::FixedWindow => format!("{window_ms}ms Window"),
+    }
+}
+
+pub fn backspace_mode_name(mode: BackspaceMode) -> &'static str {
+    match mode {
+        BackspaceMode::Normal => "Normal",
+        BackspaceMode::Confidence => "Confidence",
+        BackspaceMode::Disabled => "Off",
+    }
+}
+
+pub fn pace_caret_name(pace: PaceCaret, wpm: u32) -> String {
+    match pace {
+        PaceCaret::Off => "Off".to_string(),
+        PaceCaret::Fixed => format!("{wpm} WPM"),
+        PaceCaret::PersonalBest => "Personal Best".to_string(),
+        PaceCaret::Average => "Recent Average".to_string(),
+    }
+}
+
 pub fn test_mode_name(mode: TestMode) -> String {
     match mode {
         TestMode::Timed(seconds) => format!("{seconds} seconds"),
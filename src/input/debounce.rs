@@ -0,0 +1,80 @@
+use crate::config::KeyDebounce;
+use crate::input::keys::{KeyCode, KeyEventKind};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyDecision {
+    Accept,
+
+    Drop,
+
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct KeyState {
+    last_press: Option<Duration>,
+
+    reports_release: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Debouncer {
+    policy: KeyDebounce,
+
+    window: Duration,
+
+    keys: HashMap<KeyCode, KeyState>,
+
+    dropped: usize,
+}
+
+impl Debouncer {
+    pub fn new(policy: KeyDebounce, window: Duration) -> Self {
+        Self {
+            policy,
+            window,
+            keys: HashMap::new(),
+            dropped: 0,
+        }
+    }
+
+    pub fn set_policy(&mut self, policy: KeyDebounce, window: Duration) {
+        self.policy = policy;
+        self.window = window;
+    }
+
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    pub fn check(&mut self, code: KeyCode, kind: KeyEventKind, now: Duration) -> KeyDecision {
+        let state = self.keys.entry(code).or_default();
+
+        let within_window = state
+            .last_press
+            .is_some_and(|last| now.saturating_sub(last) < self.window);
+
+        let decision = match (self.policy, kind) {
+            (_, KeyEventKind::Release) => {
+                state.reports_release = true;
+                return KeyDecision::Ignore;
+            }
+            (_, KeyEventKind::Repeat) => return KeyDecision::Accept,
+            (KeyDebounce::Off, _) => KeyDecision::Accept,
+            (KeyDebounce::PressRelease, KeyEventKind::Press) if state.reports_release => {
+                KeyDecision::Accept
+            }
+            _ if within_window => KeyDecision::Drop,
+            _ => KeyDecision::Accept,
+        };
+
+        match decision {
+            KeyDecision::Accept => state.last_press = Some(now),
+            KeyDecision::Drop => self.dropped += 1,
+            KeyDecision::Ignore => {}
+        }
+        decision
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+
+#[cfg(target_arch = "wasm32")]
+pub use web::*;
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use bitflags::bitflags;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum KeyCode {
+        Backspace,
+        Enter,
+        Left,
+        Right,
+        Up,
+        Down,
+        Home,
+        End,
+        PageUp,
+        PageDown,
+        Tab,
+        BackTab,
+        Delete,
+        Insert,
+        F(u8),
+        Char(char),
+        Null,
+        Esc,
+    }
+
+    bitflags! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct KeyModifiers: u8 {
+            const SHIFT = 0b0000_0001;
+            const CONTROL = 0b0000_0010;
+            const ALT = 0b0000_0100;
+            const SUPER = 0b0000_1000;
+            const NONE = 0b0000_0000;
+        }
+    }
+
+    bitflags! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct KeyEventState: u8 {
+            const NONE = 0b0000_0000;
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum KeyEventKind {
+        Press,
+        Repeat,
+        Release,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct KeyEvent {
+        pub code: KeyCode,
+        pub modifiers: KeyModifiers,
+        pub kind: KeyEventKind,
+        pub state: KeyEventState,
+    }
+
+    impl KeyEvent {
+        pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+            Self {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                state: KeyEventState::NONE,
+            }
+        }
+    }
+
+    impl From<KeyCode> for KeyEvent {
+        fn from(code: KeyCode) -> Self {
+            Self::new(code, KeyModifiers::NONE)
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum MouseButton {
+        Left,
+        Right,
+        Middle,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum MouseEventKind {
+        Down(MouseButton),
+        Up(MouseButton),
+        Drag(MouseButton),
+        Moved,
+        ScrollDown,
+        ScrollUp,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct MouseEvent {
+        pub kind: MouseEventKind,
+        pub column: u16,
+        pub row: u16,
+        pub modifiers: KeyModifiers,
+    }
+}
@@ -0,0 +1,153 @@
+use crate::input::keys::{KeyCode, KeyEvent, KeyModifiers};
+use anyhow::{Context, Result, anyhow, bail};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptedKey {
+    pub at: Duration,
+
+    pub key: KeyEvent,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Script {
+    keys: VecDeque<ScriptedKey>,
+}
+
+impl Script {
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read script {}", path.display()))?;
+        Self::parse(&source).with_context(|| format!("invalid script {}", path.display()))
+    }
+
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut keys = VecDeque::new();
+        let mut last = Duration::ZERO;
+
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let key = parse_line(line).with_context(|| format!("line {}", index + 1))?;
+            if key.at < last {
+                bail!("line {}: timestamps must not go backwards", index + 1);
+            }
+            last = key.at;
+            keys.push_back(key);
+        }
+
+        Ok(Self { keys })
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn next_at(&self) -> Option<Duration> {
+        self.keys.front().map(|scripted| scripted.at)
+    }
+
+    pub fn next_due(&mut self, elapsed: Duration) -> Option<KeyEvent> {
+        if self.keys.front()?.at <= elapsed {
+            self.keys.pop_front().map(|scripted| scripted.key)
+        } else {
+            None
+        }
+    }
+}
+
+fn parse_line(line: &str) -> Result<ScriptedKey> {
+    let mut at = None;
+    let mut key = None;
+
+    for field in line.split_whitespace() {
+        let (name, value) = field
+            .split_once('=')
+            .ok_or_else(|| anyhow!("expected name=value, got '{field}'"))?;
+
+        match name {
+            "t" => at = Some(parse_time(value)?),
+            "key" => key = Some(parse_key(value)?),
+            _ => bail!("unknown field '{name}'"),
+        }
+    }
+
+    Ok(ScriptedKey {
+        at: at.ok_or_else(|| anyhow!("missing t="))?,
+        key: key.ok_or_else(|| anyhow!("missing key="))?,
+    })
+}
+
+fn parse_time(value: &str) -> Result<Duration> {
+    let invalid = || anyhow!("invalid time '{value}', expected e.g. 120ms or 1.5s");
+
+    if let Some(millis) = value.strip_suffix("ms") {
+        millis
+            .parse::<u64>()
+            .map(Duration::from_millis)
+            .map_err(|_| invalid())
+    } else if let Some(seconds) = value.strip_suffix('s') {
+        seconds
+            .parse::<f64>()
+            .ok()
+            .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+            .map(Duration::from_secs_f64)
+            .ok_or_else(invalid)
+    } else {
+        Err(invalid())
+    }
+}
+
+pub fn parse_key(value: &str) -> Result<KeyEvent> {
+    let mut modifiers = KeyModifiers::empty();
+    let mut name = value;
+
+    while let Some((modifier, rest)) = name.split_once('+').filter(|(_, rest)| !rest.is_empty()) {
+        modifiers |= match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => bail!("unknown modifier '{modifier}'"),
+        };
+        name = rest;
+    }
+
+    let mut chars = name.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match name.to_ascii_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "enter" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            function => function
+                .strip_prefix('f')
+                .and_then(|n| n.parse::<u8>().ok())
+                .filter(|n| (1..=12).contains(n))
+                .map(KeyCode::F)
+                .ok_or_else(|| anyhow!("unknown key '{name}'"))?,
+        },
+    };
+
+    Ok(KeyEvent::new(code, modifiers))
+}
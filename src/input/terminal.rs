@@ -0,0 +1,110 @@
+use super::{Debouncer, Event, KeyDecision, Script};
+use crate::AppResult;
+use crate::clock::{SharedClock, system_clock};
+use crate::config::KeyDebounce;
+use crossterm::event::{self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers};
+use std::time::Duration;
+
+enum EventSource {
+    Terminal,
+
+    Script { script: Script, started: Duration },
+}
+
+pub struct InputHandler {
+    tick_rate: Duration,
+    last_tick: Duration,
+    clock: SharedClock,
+    debouncer: Debouncer,
+    source: EventSource,
+}
+
+impl InputHandler {
+    pub fn new(tick_rate: Duration) -> Self {
+        Self::with_clock(tick_rate, system_clock())
+    }
+
+    pub fn with_clock(tick_rate: Duration, clock: SharedClock) -> Self {
+        Self {
+            tick_rate,
+            last_tick: clock.now(),
+            clock,
+            debouncer: Debouncer::new(KeyDebounce::default(), Duration::from_millis(60)),
+            source: EventSource::Terminal,
+        }
+    }
+
+    pub fn from_script(tick_rate: Duration, script: Script, clock: SharedClock) -> Self {
+        let started = clock.now();
+        let mut handler = Self::with_clock(tick_rate, clock);
+        handler.source = EventSource::Script { script, started };
+        handler
+    }
+
+    pub fn set_debounce(&mut self, policy: KeyDebounce, window: Duration) {
+        self.debouncer.set_policy(policy, window);
+    }
+
+    pub fn dropped_keys(&self) -> usize {
+        self.debouncer.dropped()
+    }
+
+    pub fn script_finished(&self) -> bool {
+        match &self.source {
+            EventSource::Terminal => false,
+            EventSource::Script { script, .. } => script.is_empty(),
+        }
+    }
+
+    fn time_until_next_event(&self, now: Duration) -> Duration {
+        let next_tick = self.last_tick + self.tick_rate;
+
+        let deadline = match &self.source {
+            EventSource::Script { script, started } => script
+                .next_at()
+                .map_or(next_tick, |at| next_tick.min(*started + at)),
+            EventSource::Terminal => next_tick,
+        };
+
+        deadline.saturating_sub(now)
+    }
+
+    pub fn poll_event(&mut self) -> AppResult<Option<Event>> {
+        let now = self.clock.now();
+        if now.saturating_sub(self.last_tick) >= self.tick_rate {
+            self.last_tick = now;
+            return Ok(Some(Event::Tick));
+        }
+
+        if let EventSource::Script { script, started } = &mut self.source
+            && let Some(key) = script.next_due(now.saturating_sub(*started))
+        {
+            return Ok(Some(Event::Key(key)));
+        }
+
+        if event::poll(self.time_until_next_event(now))? {
+            match event::read()? {
+                CrosstermEvent::Key(key) => {
+                    if matches!(self.source, EventSource::Script { .. }) && !is_interrupt(&key) {
+                        return Ok(None);
+                    }
+
+                    match self.debouncer.check(key.code, key.kind, now) {
+                        KeyDecision::Accept => Ok(Some(Event::Key(key))),
+                        KeyDecision::Drop => Ok(Some(Event::KeyDropped(key))),
+                        KeyDecision::Ignore => Ok(None),
+                    }
+                }
+                CrosstermEvent::Mouse(e) => Ok(Some(Event::Mouse(e))),
+                CrosstermEvent::Resize(w, h) => Ok(Some(Event::Resize(w, h))),
+                _ => Ok(None),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn is_interrupt(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
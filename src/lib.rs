@@ -1,16 +1,27 @@
 use anyhow::Result;
 
+pub mod cli;
+pub mod clock;
 pub mod config;
 pub mod input;
+pub mod net;
+pub mod pace;
+pub mod profile;
+pub mod replay;
+pub mod session;
 pub mod stats;
+pub mod storage;
 pub mod text;
 pub mod ui;
 pub mod util;
 pub mod version;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
 
 pub type AppResult<T> = Result<T>;
 
 pub use config::Config;
+pub use session::TypingSession;
 pub use stats::TypingStats;
 pub use text::TextSource;
 pub use ui::App;
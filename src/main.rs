@@ -1,39 +1,114 @@
+#[cfg(not(target_arch = "wasm32"))]
 use anyhow::Result;
+#[cfg(not(target_arch = "wasm32"))]
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    cursor::SetCursorStyle,
+    event::{
+        DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+        supports_keyboard_enhancement,
+    },
 };
+#[cfg(not(target_arch = "wasm32"))]
 use ratatui::{Terminal, backend::CrosstermBackend};
+#[cfg(not(target_arch = "wasm32"))]
 use std::{
     io::{self},
     time::Duration,
 };
+#[cfg(not(target_arch = "wasm32"))]
 use tuitype::{
     AppResult,
-    config::Config,
-    input::{Event, InputHandler},
+    cli::{CliCommand, RaceMode, USAGE, version_string},
+    clock::system_clock,
+    config::{CaretStyle, Config, LocalConfig},
+    input::{Event, InputHandler, Script},
+    net::Race,
     ui::{App, render},
 };
 
+#[cfg(not(target_arch = "wasm32"))]
 fn main() -> Result<()> {
+    let args = match CliCommand::parse(std::env::args().skip(1))? {
+        CliCommand::Run(args) => args,
+        CliCommand::Help => {
+            println!("{USAGE}");
+            return Ok(());
+        }
+        CliCommand::Version => {
+            println!("{}", version_string());
+            return Ok(());
+        }
+    };
+
+    let script = args.script.as_deref().map(Script::load).transpose()?;
+    let race = match &args.race {
+        Some(RaceMode::Host(port)) => Some(Race::host(*port, &args.player_name())?),
+        Some(RaceMode::Join(addr)) => Some(Race::join(addr.as_str(), &args.player_name())?),
+        None => None,
+    };
+
+    let mut app = App::new(Config::default());
+    app.set_storage(args.storage());
+    let mut warnings = app.load_profile(args.profile.as_deref())?;
+    match LocalConfig::discover() {
+        Ok(Some(local)) => warnings.extend(app.apply_local_config(local)),
+        Ok(None) => {}
+        Err(err) => warnings.push(format!("{err:#}. Project settings were not applied.")),
+    }
+    app.show_config_warnings(warnings);
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let keyboard_enhancement = supports_keyboard_enhancement().unwrap_or(false);
+    if keyboard_enhancement {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(
+                KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                    | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+            )
+        )?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let config = Config::load().unwrap_or_default();
-
-    let app = App::new(config);
-    let mut input_handler = InputHandler::new(Duration::from_millis(33));
+    if args.seed.is_some() {
+        app.set_seed(args.seed);
+    }
+    if let Some(race) = race {
+        app.set_race(race);
+    }
+    let tick_rate = Duration::from_millis(33);
+    let mut input_handler = match script {
+        Some(script) => InputHandler::from_script(tick_rate, script, system_clock()),
+        None => InputHandler::new(tick_rate),
+    };
 
-    let res = run_app(&mut terminal, app, &mut input_handler);
+    let res = run_app(
+        &mut terminal,
+        app,
+        &mut input_handler,
+        args.exit_after_script,
+    );
 
     terminal.show_cursor()?;
     let backend = terminal.backend_mut();
+    if keyboard_enhancement {
+        execute!(backend, PopKeyboardEnhancementFlags)?;
+    }
     disable_raw_mode()?;
-    execute!(backend, LeaveAlternateScreen, DisableMouseCapture)?;
+    execute!(
+        backend,
+        SetCursorStyle::DefaultUserShape,
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
 
     if let Err(err) = res {
         println!("Error: {err:?}")
@@ -42,24 +117,57 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn run_app<B: ratatui::backend::Backend>(
+#[cfg(not(target_arch = "wasm32"))]
+fn cursor_shape(style: CaretStyle) -> SetCursorStyle {
+    match style {
+        CaretStyle::Block => SetCursorStyle::SteadyBlock,
+        CaretStyle::Underline => SetCursorStyle::SteadyUnderScore,
+        CaretStyle::Bar | CaretStyle::Off => SetCursorStyle::SteadyBar,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn run_app<B: ratatui::backend::Backend + io::Write>(
     terminal: &mut Terminal<B>,
     mut app: App,
     input_handler: &mut InputHandler,
+    exit_after_script: bool,
 ) -> AppResult<()> {
+    let mut cursor_style = None;
+
     while app.running {
-        terminal.draw(|f| render(&app, f).expect("Failed to render UI"))?;
+        input_handler.set_debounce(
+            app.config.key_debounce,
+            Duration::from_millis(app.config.debounce_ms),
+        );
+
+        if app.take_redraw() {
+            let wanted_cursor_style = app.terminal_cursor_style();
+            if wanted_cursor_style != cursor_style {
+                if let Some(style) = wanted_cursor_style {
+                    execute!(terminal.backend_mut(), cursor_shape(style))?;
+                }
+                cursor_style = wanted_cursor_style;
+            }
+
+            terminal.draw(|f| render(&app, f).expect("Failed to render UI"))?;
+        }
 
         match input_handler.poll_event()? {
-            Some(Event::Key(key)) => app.handle_events(Event::Key(key))?,
-            Some(Event::Mouse(mouse)) => app.handle_events(Event::Mouse(mouse))?,
-            Some(Event::Resize(w, h)) => app.handle_events(Event::Resize(w, h))?,
             Some(Event::Tick) => app.tick()?,
-            None => {
-                std::thread::sleep(Duration::from_millis(1));
-            }
+            Some(event) => app.handle_events(event)?,
+            None => {}
+        }
+
+        if exit_after_script && input_handler.script_finished() {
+            app.tick()?;
+            terminal.draw(|f| render(&app, f).expect("Failed to render UI"))?;
+            break;
         }
     }
 
     Ok(())
 }
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -0,0 +1,69 @@
+use crate::net::protocol::{
+    ClientMessage, ServerMessage, WRITE_TIMEOUT, read_message, spawn_writer, write_message,
+};
+use anyhow::Result;
+use std::io::BufReader;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+
+pub struct RaceClient {
+    writer: Sender<ClientMessage>,
+
+    messages: Receiver<ServerMessage>,
+
+    connected: bool,
+}
+
+impl RaceClient {
+    pub fn connect<A: ToSocketAddrs>(addr: A, name: &str) -> Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+
+        let mut writer = stream.try_clone()?;
+        writer.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        write_message(
+            &mut writer,
+            &ClientMessage::Join {
+                name: name.to_string(),
+            },
+        )?;
+
+        let (sender, messages) = mpsc::channel();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(stream);
+            while let Ok(Some(message)) = read_message::<_, ServerMessage>(&mut reader) {
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            writer: spawn_writer(writer),
+            messages,
+            connected: true,
+        })
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    pub fn send(&mut self, message: &ClientMessage) {
+        if self.connected && self.writer.send(message.clone()).is_err() {
+            self.connected = false;
+        }
+    }
+
+    pub fn try_recv(&mut self) -> Option<ServerMessage> {
+        match self.messages.try_recv() {
+            Ok(message) => Some(message),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                self.connected = false;
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+mod client;
+mod protocol;
+mod server;
+
+use anyhow::Result;
+use std::cmp::Ordering;
+use std::net::{Ipv4Addr, SocketAddr, ToSocketAddrs};
+
+pub use client::RaceClient;
+pub use protocol::{
+    ClientMessage, DEFAULT_PORT, MAX_LINE, PlayerState, RaceResult, RaceSettings, ServerMessage,
+};
+pub use server::RaceServer;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RacePhase {
+    #[default]
+    Lobby,
+
+    Racing,
+
+    Finished,
+}
+
+pub struct Race {
+    client: RaceClient,
+
+    server: Option<RaceServer>,
+
+    pub player_id: Option<u32>,
+
+    pub players: Vec<PlayerState>,
+
+    pub phase: RacePhase,
+
+    pub settings: Option<RaceSettings>,
+
+    pub address: String,
+
+    last_position: Option<usize>,
+
+    updated: bool,
+}
+
+impl Race {
+    pub fn host(port: u16, name: &str) -> Result<Self> {
+        let server = RaceServer::bind((Ipv4Addr::UNSPECIFIED, port))?;
+        let local = SocketAddr::from((Ipv4Addr::LOCALHOST, server.local_addr().port()));
+
+        let mut race = Self::new(RaceClient::connect(local, name)?, local.to_string());
+        race.address = format!("port {}", server.local_addr().port());
+        race.server = Some(server);
+        Ok(race)
+    }
+
+    pub fn join<A: ToSocketAddrs + ToString>(addr: A, name: &str) -> Result<Self> {
+        let address = addr.to_string();
+        Ok(Self::new(RaceClient::connect(addr, name)?, address))
+    }
+
+    fn new(client: RaceClient, address: String) -> Self {
+        Self {
+            client,
+            server: None,
+            player_id: None,
+            players: Vec::new(),
+            phase: RacePhase::Lobby,
+            settings: None,
+            address,
+            last_position: None,
+            updated: false,
+        }
+    }
+
+    pub fn is_host(&self) -> bool {
+        self.server.is_some()
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.client.is_connected()
+    }
+
+    pub fn start(&mut self, race: RaceSettings) -> bool {
+        match &self.server {
+            Some(server) => {
+                server.start_race(race);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn poll(&mut self) -> Option<RaceSettings> {
+        let mut started = None;
+
+        while let Some(message) = self.client.try_recv() {
+            self.updated = true;
+            match message {
+                ServerMessage::Welcome { player_id } => self.player_id = Some(player_id),
+                ServerMessage::Players { players } => self.players = players,
+                ServerMessage::Start { race } => {
+                    self.phase = RacePhase::Racing;
+                    self.last_position = None;
+                    self.settings = Some(race.clone());
+                    started = Some(race);
+                }
+                ServerMessage::Standings { players } => {
+                    self.players = players;
+                    self.phase = RacePhase::Finished;
+                }
+            }
+        }
+
+        started
+    }
+
+    pub fn take_updated(&mut self) -> bool {
+        std::mem::take(&mut self.updated)
+    }
+
+    pub fn report_progress(&mut self, position: usize, length: usize, wpm: f64) {
+        if self.phase != RacePhase::Racing || self.last_position == Some(position) {
+            return;
+        }
+
+        self.last_position = Some(position);
+        self.client.send(&ClientMessage::Progress {
+            position,
+            length,
+            wpm,
+        });
+    }
+
+    pub fn report_finished(&mut self, wpm: f64, accuracy: f64, elapsed_ms: u64) {
+        if self.phase == RacePhase::Racing {
+            self.client.send(&ClientMessage::Finished {
+                wpm,
+                accuracy,
+                elapsed_ms,
+            });
+        }
+    }
+
+    pub fn standings(&self) -> Vec<PlayerState> {
+        standings(&self.players)
+    }
+}
+
+pub fn standings(players: &[PlayerState]) -> Vec<PlayerState> {
+    let mut players = players.to_vec();
+    players.sort_by(|a, b| match (&a.result, &b.result) {
+        (Some(a), Some(b)) => b.wpm.partial_cmp(&a.wpm).unwrap_or(Ordering::Equal),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => b
+            .progress()
+            .partial_cmp(&a.progress())
+            .unwrap_or(Ordering::Equal),
+    });
+    players
+}
@@ -0,0 +1,133 @@
+use crate::config::{Difficulty, TestMode};
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::io::{BufRead, Read, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::Duration;
+
+pub const DEFAULT_PORT: u16 = 7878;
+
+pub const MAX_LINE: u64 = 1 << 20;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Join {
+        name: String,
+    },
+
+    Progress {
+        position: usize,
+        length: usize,
+        wpm: f64,
+    },
+
+    Finished {
+        wpm: f64,
+        accuracy: f64,
+        elapsed_ms: u64,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RaceSettings {
+    pub seed: u64,
+
+    pub test_mode: TestMode,
+
+    pub difficulty: Difficulty,
+
+    pub custom_text: Option<String>,
+
+    pub word_list: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlayerState {
+    pub id: u32,
+
+    pub name: String,
+
+    pub position: usize,
+
+    pub length: usize,
+
+    pub wpm: f64,
+
+    pub result: Option<RaceResult>,
+}
+
+impl PlayerState {
+    pub fn progress(&self) -> f64 {
+        if self.result.is_some() {
+            1.0
+        } else if self.length == 0 {
+            0.0
+        } else {
+            (self.position as f64 / self.length as f64).min(1.0)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RaceResult {
+    pub wpm: f64,
+
+    pub accuracy: f64,
+
+    pub elapsed_ms: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Welcome { player_id: u32 },
+
+    Players { players: Vec<PlayerState> },
+
+    Start { race: RaceSettings },
+
+    Standings { players: Vec<PlayerState> },
+}
+
+pub fn write_message<W: Write, M: Serialize>(writer: &mut W, message: &M) -> Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub fn spawn_writer<M: Serialize + Send + 'static>(mut stream: TcpStream) -> Sender<M> {
+    let (sender, receiver) = mpsc::channel::<M>();
+    thread::spawn(move || {
+        for message in receiver {
+            if write_message(&mut stream, &message).is_err() {
+                break;
+            }
+        }
+        stream.shutdown(Shutdown::Both).ok();
+    });
+    sender
+}
+
+pub fn read_message<R: BufRead, M: DeserializeOwned>(reader: &mut R) -> Result<Option<M>> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let read = reader.by_ref().take(MAX_LINE).read_line(&mut line)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if read as u64 == MAX_LINE && !line.ends_with('\n') {
+            bail!("message is longer than {MAX_LINE} bytes");
+        }
+        if !line.trim().is_empty() {
+            return Ok(Some(serde_json::from_str(line.trim())?));
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::net::protocol::{
+    ClientMessage, PlayerState, RaceResult, RaceSettings, ServerMessage, WRITE_TIMEOUT,
+    read_message, spawn_writer,
+};
+use crate::net::standings;
+use anyhow::Result;
+use std::io::BufReader;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+
+struct Connection {
+    id: u32,
+
+    sender: Sender<ServerMessage>,
+}
+
+#[derive(Default)]
+struct ServerState {
+    next_id: u32,
+
+    connections: Vec<Connection>,
+
+    players: Vec<PlayerState>,
+
+    race: Option<RaceSettings>,
+
+    racing: bool,
+}
+
+impl ServerState {
+    fn broadcast(&mut self, message: &ServerMessage) {
+        self.connections
+            .retain(|connection| connection.sender.send(message.clone()).is_ok());
+    }
+
+    fn broadcast_players(&mut self) {
+        let players = self.players.clone();
+        self.broadcast(&ServerMessage::Players { players });
+    }
+
+    fn finish_if_everyone_done(&mut self) {
+        if self.racing
+            && !self.players.is_empty()
+            && self.players.iter().all(|player| player.result.is_some())
+        {
+            self.racing = false;
+            let players = standings(&self.players);
+            self.broadcast(&ServerMessage::Standings { players });
+        }
+    }
+}
+
+pub struct RaceServer {
+    local_addr: SocketAddr,
+
+    state: Arc<Mutex<ServerState>>,
+}
+
+impl RaceServer {
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(ServerState::default()));
+
+        let accept_state = Arc::clone(&state);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let state = Arc::clone(&accept_state);
+                thread::spawn(move || handle_connection(stream, state));
+            }
+        });
+
+        Ok(Self { local_addr, state })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    pub fn player_count(&self) -> usize {
+        lock(&self.state).players.len()
+    }
+
+    pub fn is_racing(&self) -> bool {
+        lock(&self.state).racing
+    }
+
+    pub fn start_race(&self, race: RaceSettings) {
+        let mut state = lock(&self.state);
+
+        for player in &mut state.players {
+            player.position = 0;
+            player.length = 0;
+            player.wpm = 0.0;
+            player.result = None;
+        }
+        state.race = Some(race.clone());
+        state.racing = true;
+
+        state.broadcast(&ServerMessage::Start { race });
+        state.broadcast_players();
+    }
+}
+
+fn lock(state: &Mutex<ServerState>) -> MutexGuard<'_, ServerState> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn handle_connection(stream: TcpStream, state: Arc<Mutex<ServerState>>) {
+    let Ok(writer) = stream.try_clone() else {
+        return;
+    };
+    if writer.set_write_timeout(Some(WRITE_TIMEOUT)).is_err() {
+        return;
+    }
+    let mut reader = BufReader::new(stream);
+
+    let name = match read_message::<_, ClientMessage>(&mut reader) {
+        Ok(Some(ClientMessage::Join { name })) => name,
+        _ => return,
+    };
+
+    let id = {
+        let mut state = lock(&state);
+        let id = state.next_id;
+        state.next_id += 1;
+
+        let connection = Connection {
+            id,
+            sender: spawn_writer(writer),
+        };
+        if connection
+            .sender
+            .send(ServerMessage::Welcome { player_id: id })
+            .is_err()
+        {
+            return;
+        }
+        if state.racing
+            && let Some(race) = state.race.clone()
+            && connection
+                .sender
+                .send(ServerMessage::Start { race })
+                .is_err()
+        {
+            return;
+        }
+
+        state.connections.push(connection);
+        state.players.push(PlayerState {
+            id,
+            name,
+            position: 0,
+            length: 0,
+            wpm: 0.0,
+            result: None,
+        });
+        state.broadcast_players();
+        id
+    };
+
+    while let Ok(Some(message)) = read_message::<_, ClientMessage>(&mut reader) {
+        let mut state = lock(&state);
+        let racing = state.racing;
+        let Some(player) = state.players.iter_mut().find(|player| player.id == id) else {
+            break;
+        };
+
+        match message {
+            ClientMessage::Join { name } => player.name = name,
+            ClientMessage::Progress {
+                position,
+                length,
+                wpm,
+            } if racing && player.result.is_none() => {
+                player.position = position;
+                player.length = length;
+                player.wpm = wpm;
+            }
+            ClientMessage::Finished {
+                wpm,
+                accuracy,
+                elapsed_ms,
+            } if racing && player.result.is_none() => {
+                player.position = player.length;
+                player.wpm = wpm;
+                player.result = Some(RaceResult {
+                    wpm,
+                    accuracy,
+                    elapsed_ms,
+                });
+            }
+            _ => continue,
+        }
+
+        state.broadcast_players();
+        state.finish_if_everyone_done();
+    }
+
+    let mut state = lock(&state);
+    state.connections.retain(|connection| connection.id != id);
+    state.players.retain(|player| player.id != id);
+    state.broadcast_players();
+    state.finish_if_everyone_done();
+}
@@ -0,0 +1,58 @@
+use crate::config::{Config, PaceCaret, test_mode_name};
+use crate::stats::TestHistory;
+
+pub const PACE_AVERAGE_RUNS: usize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Ghost {
+    Steady { chars_per_second: f64 },
+
+    Recorded { timeline: Vec<(u64, usize)> },
+}
+
+impl Ghost {
+    pub fn from_config(config: &Config, history: Option<&TestHistory>) -> Option<Self> {
+        match config.pace_caret {
+            PaceCaret::Off => None,
+            PaceCaret::Fixed => Some(Self::steady(config.pace_caret_wpm as f64)),
+            PaceCaret::PersonalBest => {
+                let best = history?.personal_best(&test_mode_name(config.test_mode))?;
+                if best.keystrokes.is_empty() {
+                    Some(Self::steady(best.wpm))
+                } else {
+                    let timeline = best
+                        .keystrokes
+                        .iter()
+                        .map(|k| (k.elapsed_ms, k.cursor))
+                        .collect();
+                    Some(Ghost::Recorded { timeline })
+                }
+            }
+            PaceCaret::Average => history?
+                .recent_average_wpm(PACE_AVERAGE_RUNS)
+                .map(Self::steady),
+        }
+    }
+
+    fn steady(wpm: f64) -> Self {
+        Ghost::Steady {
+            chars_per_second: wpm * 5.0 / 60.0,
+        }
+    }
+
+    pub fn position(&self, elapsed_ms: u64) -> usize {
+        match self {
+            Ghost::Steady { chars_per_second } => {
+                (chars_per_second * elapsed_ms as f64 / 1000.0) as usize
+            }
+            Ghost::Recorded { timeline } => {
+                let reached = timeline.partition_point(|&(at, _)| at <= elapsed_ms);
+                if reached == 0 {
+                    0
+                } else {
+                    timeline[reached - 1].1
+                }
+            }
+        }
+    }
+}
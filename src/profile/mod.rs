@@ -0,0 +1,89 @@
+use crate::storage::{PrefixedStorage, SharedStorage, Storage};
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+const MAX_NAME_LEN: usize = 32;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profiles {
+    pub names: Vec<String>,
+
+    pub last_used: Option<String>,
+}
+
+impl Profiles {
+    pub const STORAGE_KEY: &str = "profiles.json";
+
+    pub fn save_to(&self, storage: &dyn Storage) -> Result<()> {
+        let serialized = serde_json::to_string_pretty(self)?;
+        storage.write(Self::STORAGE_KEY, &serialized)
+    }
+
+    pub fn load_from(storage: &dyn Storage) -> Result<Self> {
+        match storage.read(Self::STORAGE_KEY)? {
+            Some(contents) => Ok(serde_json::from_str(&contents)?),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names = vec![DEFAULT_PROFILE];
+        names.extend(
+            self.names
+                .iter()
+                .map(String::as_str)
+                .filter(|name| *name != DEFAULT_PROFILE),
+        );
+        names
+    }
+
+    pub fn last_used(&self) -> &str {
+        self.last_used.as_deref().unwrap_or(DEFAULT_PROFILE)
+    }
+
+    pub fn select(&mut self, name: &str) -> bool {
+        let mut changed = false;
+        if name != DEFAULT_PROFILE && !self.names.iter().any(|known| known == name) {
+            self.names.push(name.to_string());
+            self.names.sort();
+            changed = true;
+        }
+        if self.last_used.as_deref() != Some(name) {
+            self.last_used = Some(name.to_string());
+            changed = true;
+        }
+        changed
+    }
+}
+
+pub fn validate_name(name: &str) -> Result<String> {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() {
+        bail!("Profile name can't be empty");
+    }
+    if name.len() > MAX_NAME_LEN {
+        bail!("Profile names can be at most {MAX_NAME_LEN} characters");
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        bail!("Use only letters, digits, - and _");
+    }
+    Ok(name)
+}
+
+pub fn profile_storage(root: &SharedStorage, name: &str) -> SharedStorage {
+    if name == DEFAULT_PROFILE {
+        root.clone()
+    } else {
+        Arc::new(PrefixedStorage::new(
+            root.clone(),
+            format!("profiles/{name}/"),
+        ))
+    }
+}
@@ -0,0 +1,162 @@
+use crate::stats::{Keystroke, KeystrokeKind, TestResult};
+use std::time::Duration;
+
+pub const REPLAY_SPEEDS: [f64; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+
+pub struct Replay {
+    pub text: Vec<char>,
+
+    pub typed: Vec<char>,
+
+    pub position_ms: f64,
+
+    pub speed_index: usize,
+
+    pub paused: bool,
+
+    keystrokes: Vec<Keystroke>,
+
+    applied: usize,
+
+    last_update: Option<Duration>,
+}
+
+impl Replay {
+    pub fn new(result: &TestResult) -> Self {
+        Self {
+            text: result.text.chars().collect(),
+            typed: Vec::new(),
+            position_ms: 0.0,
+            speed_index: 2,
+            paused: false,
+            keystrokes: result.keystrokes.clone(),
+            applied: 0,
+            last_update: None,
+        }
+    }
+
+    pub fn can_replay(result: &TestResult) -> bool {
+        !result.text.is_empty() && !result.keystrokes.is_empty()
+    }
+
+    pub fn speed(&self) -> f64 {
+        REPLAY_SPEEDS[self.speed_index]
+    }
+
+    pub fn cursor_pos(&self) -> usize {
+        self.typed.len()
+    }
+
+    pub fn applied_keystrokes(&self) -> usize {
+        self.applied
+    }
+
+    pub fn total_keystrokes(&self) -> usize {
+        self.keystrokes.len()
+    }
+
+    pub fn duration_ms(&self) -> u64 {
+        self.keystrokes.last().map(|k| k.elapsed_ms).unwrap_or(0)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.applied >= self.keystrokes.len()
+    }
+
+    pub fn update(&mut self, now: Duration) {
+        let last_update = self.last_update.replace(now);
+
+        if self.paused || self.is_finished() {
+            return;
+        }
+
+        if let Some(last) = last_update {
+            self.position_ms += now.saturating_sub(last).as_secs_f64() * 1000.0 * self.speed();
+        }
+
+        while self.applied < self.keystrokes.len()
+            && (self.keystrokes[self.applied].elapsed_ms as f64) <= self.position_ms
+        {
+            self.apply_next();
+        }
+
+        if self.is_finished() {
+            self.paused = true;
+        }
+    }
+
+    pub fn toggle_pause(&mut self) {
+        if self.is_finished() {
+            self.restart();
+        } else {
+            self.paused = !self.paused;
+        }
+    }
+
+    pub fn faster(&mut self) {
+        self.speed_index = (self.speed_index + 1).min(REPLAY_SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed_index = self.speed_index.saturating_sub(1);
+    }
+
+    pub fn step_forward(&mut self) {
+        self.paused = true;
+        if !self.is_finished() {
+            self.apply_next();
+        }
+    }
+
+    pub fn step_back(&mut self) {
+        self.paused = true;
+        let target = self.applied.saturating_sub(1);
+        self.rewind();
+        while self.applied < target {
+            self.apply_next();
+        }
+    }
+
+    pub fn restart(&mut self) {
+        self.rewind();
+        self.paused = false;
+    }
+
+    fn rewind(&mut self) {
+        self.typed.clear();
+        self.position_ms = 0.0;
+        self.applied = 0;
+    }
+
+    fn apply_next(&mut self) {
+        let keystroke = self.keystrokes[self.applied];
+        self.applied += 1;
+        self.position_ms = self.position_ms.max(keystroke.elapsed_ms as f64);
+
+        match keystroke.key {
+            KeystrokeKind::Backspace => {
+                self.typed.pop();
+            }
+            KeystrokeKind::DeleteWord => {
+                self.typed.truncate(keystroke.cursor);
+            }
+            KeystrokeKind::Char(c) => {
+                let typed_len = self.cursor_pos();
+                let added = keystroke.cursor.saturating_sub(typed_len);
+
+                if added == 0 {
+                    return;
+                }
+
+                if c == ' ' {
+                    for &target_char in self.text.iter().skip(typed_len).take(added - 1) {
+                        let error_char = if target_char == 'x' { 'y' } else { 'x' };
+                        self.typed.push(error_char);
+                    }
+                }
+
+                self.typed.push(c);
+            }
+        }
+    }
+}
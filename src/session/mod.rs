@@ -0,0 +1,397 @@
+use crate::clock::{SharedClock, system_clock};
+use crate::config::{BackspaceMode, Config, TestMode};
+use crate::stats::{Keystroke, KeystrokeKind, TypingStats};
+use crate::text::TextSource;
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SessionSnapshot {
+    pub target_text: String,
+
+    pub typed_text: String,
+
+    pub cursor_pos: usize,
+
+    pub wpm: f64,
+
+    pub raw_wpm: f64,
+
+    pub accuracy: f64,
+
+    pub correct_chars: usize,
+
+    pub incorrect_chars: usize,
+
+    pub elapsed_seconds: f64,
+
+    pub time_remaining: Option<u32>,
+
+    pub started: bool,
+
+    pub complete: bool,
+
+    pub end_reason: Option<String>,
+
+    pub dropped_keys: usize,
+}
+
+pub struct TypingSession {
+    pub text_source: TextSource,
+
+    pub typed_chars: Vec<char>,
+
+    pub cursor_pos: usize,
+
+    pub stats: TypingStats,
+
+    pub start_time: Option<Duration>,
+
+    pub end_time: Option<Duration>,
+
+    pub test_complete: bool,
+
+    pub time_remaining: Option<u32>,
+
+    pub test_end_reason: Option<String>,
+
+    pub keystrokes: Vec<Keystroke>,
+
+    pub test_mode: TestMode,
+
+    pub end_on_first_error: bool,
+
+    pub backspace_mode: BackspaceMode,
+
+    pub dropped_keys: usize,
+
+    clock: SharedClock,
+}
+
+impl TypingSession {
+    pub fn new(config: &Config) -> Self {
+        Self::with_clock(config, system_clock())
+    }
+
+    pub fn with_clock(config: &Config, clock: SharedClock) -> Self {
+        Self::with_text_source(config, TextSource::new(config), clock)
+    }
+
+    pub fn with_text_source(config: &Config, text_source: TextSource, clock: SharedClock) -> Self {
+        let time_remaining = match config.test_mode {
+            TestMode::Timed(seconds) => Some(seconds),
+            _ => None,
+        };
+
+        Self {
+            text_source,
+            typed_chars: Vec::new(),
+            cursor_pos: 0,
+            stats: TypingStats::default(),
+            start_time: None,
+            end_time: None,
+            test_complete: false,
+            time_remaining,
+            test_end_reason: None,
+            keystrokes: Vec::new(),
+            test_mode: config.test_mode,
+            end_on_first_error: config.end_on_first_error,
+            backspace_mode: config.backspace_mode,
+            dropped_keys: 0,
+            clock,
+        }
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        if self.test_complete {
+            return;
+        }
+
+        self.start_if_needed();
+        self.apply_char(c);
+        self.record_keystroke(KeystrokeKind::Char(c));
+    }
+
+    pub fn skip_word(&mut self) {
+        self.type_char(' ');
+    }
+
+    pub fn backspace(&mut self) {
+        if self.test_complete || !self.can_delete() {
+            return;
+        }
+
+        self.delete_char();
+        self.stats.sample(self.clock.now());
+        self.record_keystroke(KeystrokeKind::Backspace);
+    }
+
+    pub fn delete_word(&mut self) {
+        if self.test_complete || !self.can_delete() {
+            return;
+        }
+
+        while self.typed_chars.last().is_some_and(|c| c.is_whitespace()) && self.can_delete() {
+            self.delete_char();
+        }
+        while self.typed_chars.last().is_some_and(|c| !c.is_whitespace()) && self.can_delete() {
+            self.delete_char();
+        }
+
+        self.stats.sample(self.clock.now());
+        self.record_keystroke(KeystrokeKind::DeleteWord);
+    }
+
+    pub fn can_delete(&self) -> bool {
+        let Some(&last) = self.typed_chars.last() else {
+            return false;
+        };
+
+        match self.backspace_mode {
+            BackspaceMode::Normal => true,
+            BackspaceMode::Confidence => {
+                last != ' ' || !self.is_word_correct(self.typed_chars.len() - 1)
+            }
+            BackspaceMode::Disabled => false,
+        }
+    }
+
+    pub fn tick(&mut self, now: Duration) {
+        let Some(start) = self.start_time else {
+            return;
+        };
+        if self.test_complete {
+            return;
+        }
+
+        if let TestMode::Timed(seconds) = self.test_mode {
+            let elapsed = now.saturating_sub(start).as_secs() as u32;
+            if elapsed >= seconds {
+                self.time_remaining = Some(0);
+                self.complete(now);
+            } else {
+                self.time_remaining = Some(seconds - elapsed);
+
+                if self.text_source.should_add_more_words(self.cursor_pos) {
+                    self.text_source.add_more_words();
+                }
+            }
+        } else {
+            let is_word_limit_reached = if self.text_source.is_scrollable {
+                self.text_source.is_complete() && self.typed_chars.len() >= self.text_source.len()
+            } else {
+                self.typed_chars.len() >= self.text_source.len()
+            };
+
+            if is_word_limit_reached {
+                self.complete(now);
+            }
+        }
+
+        if !self.test_complete {
+            let elapsed = now.saturating_sub(start).as_secs_f64();
+            if elapsed > 0.0 {
+                self.stats.calculate_wpm(elapsed);
+            }
+        }
+    }
+
+    pub fn snapshot(&self) -> SessionSnapshot {
+        let elapsed_seconds = match (self.start_time, self.end_time) {
+            (Some(start), Some(end)) => end.saturating_sub(start).as_secs_f64(),
+            (Some(start), None) => self.clock.now().saturating_sub(start).as_secs_f64(),
+            _ => 0.0,
+        };
+
+        SessionSnapshot {
+            target_text: self.text_source.full_text().to_string(),
+            typed_text: self.typed_text(),
+            cursor_pos: self.cursor_pos,
+            wpm: self.stats.wpm,
+            raw_wpm: self.stats.raw_wpm,
+            accuracy: self.stats.accuracy,
+            correct_chars: self.stats.correct_chars,
+            incorrect_chars: self.stats.incorrect_chars,
+            elapsed_seconds,
+            time_remaining: self.time_remaining,
+            started: self.start_time.is_some(),
+            complete: self.test_complete,
+            end_reason: self.test_end_reason.clone(),
+            dropped_keys: self.dropped_keys,
+        }
+    }
+
+    pub fn typed_text(&self) -> String {
+        self.typed_chars.iter().collect()
+    }
+
+    pub fn now(&self) -> Duration {
+        self.clock.now()
+    }
+
+    pub fn duration_seconds(&self) -> f64 {
+        match (self.start_time, self.end_time) {
+            (Some(start), Some(end)) => end.saturating_sub(start).as_secs_f64(),
+            _ => 0.0,
+        }
+    }
+
+    fn start_if_needed(&mut self) {
+        if self.start_time.is_none() {
+            self.start_time = Some(self.clock.now());
+
+            if let TestMode::Timed(seconds) = self.test_mode {
+                self.time_remaining = Some(seconds);
+            }
+        }
+    }
+
+    fn apply_char(&mut self, c: char) {
+        if self.text_source.should_add_more_words(self.cursor_pos) {
+            self.text_source.add_more_words();
+        }
+
+        let target_len = self.text_source.len();
+        let target_char = self.text_source.char_at(self.cursor_pos);
+
+        if c == ' ' {
+            let is_timed_test = matches!(self.test_mode, TestMode::Timed(_));
+            if !is_timed_test && self.cursor_pos >= target_len && !self.text_source.is_scrollable {
+                return;
+            }
+
+            if self.end_on_first_error && self.cursor_pos < target_len && target_char != Some(' ') {
+                self.push_typed(' ');
+                self.stats.sample(self.clock.now());
+
+                self.complete(self.clock.now());
+                return;
+            }
+
+            if let TestMode::Quote = self.test_mode {
+                if self.cursor_pos < target_len {
+                    if target_char == Some(' ') {
+                        self.push_typed(' ');
+                    } else {
+                        self.fill_rest_of_word();
+
+                        self.push_typed(' ');
+
+                        if self.end_on_first_error {
+                            self.complete(self.clock.now());
+                            return;
+                        }
+                    }
+                } else {
+                    if self.text_source.is_complete() {
+                        self.complete(self.clock.now());
+                        return;
+                    }
+
+                    if self.text_source.should_add_more_words(self.cursor_pos) {
+                        self.text_source.add_more_words();
+                        return self.apply_char(c);
+                    }
+                }
+            } else {
+                if self.cursor_pos < target_len {
+                    self.fill_rest_of_word();
+                }
+
+                self.push_typed(' ');
+            }
+        } else {
+            let is_timed_test = matches!(self.test_mode, TestMode::Timed(_));
+            if !is_timed_test && self.cursor_pos >= target_len {
+                if !self.text_source.is_scrollable || self.text_source.is_complete() {
+                    return;
+                }
+
+                if self.text_source.should_add_more_words(self.cursor_pos) {
+                    self.text_source.add_more_words();
+                    return self.apply_char(c);
+                }
+            }
+
+            if self.end_on_first_error
+                && let Some(target_char) = target_char
+                && c != target_char
+            {
+                self.push_typed(c);
+                self.stats.sample(self.clock.now());
+
+                self.complete(self.clock.now());
+                return;
+            }
+
+            self.push_typed(c);
+        }
+
+        if c == ' ' || self.cursor_pos.is_multiple_of(5) {
+            self.stats.sample(self.clock.now());
+        }
+
+        self.tick(self.clock.now());
+    }
+
+    fn fill_rest_of_word(&mut self) {
+        while let Some(target_char) = self.text_source.char_at(self.cursor_pos)
+            && target_char != ' '
+        {
+            let error_char = if target_char == 'x' { 'y' } else { 'x' };
+            self.push_typed(error_char);
+        }
+    }
+
+    fn is_word_correct(&self, end: usize) -> bool {
+        let start = self.typed_chars[..end]
+            .iter()
+            .rposition(|&c| c == ' ')
+            .map_or(0, |i| i + 1);
+
+        (start..=end).all(|i| self.text_source.char_at(i) == Some(self.typed_chars[i]))
+    }
+
+    fn delete_char(&mut self) {
+        self.pop_typed();
+        self.stats.corrections += 1;
+    }
+
+    fn push_typed(&mut self, c: char) {
+        let expected = self.text_source.char_at(self.typed_chars.len());
+        self.typed_chars.push(c);
+        self.cursor_pos += 1;
+        self.stats.add_char(c, expected);
+    }
+
+    fn pop_typed(&mut self) {
+        if let Some(c) = self.typed_chars.pop() {
+            let expected = self.text_source.char_at(self.typed_chars.len());
+            self.cursor_pos = self.cursor_pos.saturating_sub(1);
+            self.stats.remove_char(c, expected);
+        }
+    }
+
+    fn record_keystroke(&mut self, key: KeystrokeKind) {
+        if let Some(start) = self.start_time {
+            self.keystrokes.push(Keystroke {
+                elapsed_ms: self.clock.now().saturating_sub(start).as_millis() as u64,
+                key,
+                cursor: self.cursor_pos,
+            });
+        }
+    }
+
+    fn complete(&mut self, now: Duration) {
+        if !self.test_complete {
+            self.test_complete = true;
+            self.end_time = Some(now);
+
+            if self.end_on_first_error && self.stats.incorrect_chars > 0 {
+                self.test_end_reason = Some("Test ended due to typing error".to_string());
+            }
+
+            self.stats.finalize();
+        }
+    }
+}
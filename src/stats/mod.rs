@@ -1,6 +1,8 @@
-use crate::text::TextSource;
+use crate::storage::Storage;
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::time::Instant;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct TypingStats {
@@ -24,8 +26,11 @@ pub struct TypingStats {
 
     pub raw_wpm_samples: Vec<f64>,
 
+    #[serde(default)]
+    pub corrections: usize,
+
     #[serde(skip)]
-    pub last_sample_time: Option<Instant>,
+    pub last_sample_time: Option<Duration>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -40,6 +45,8 @@ pub struct SerializableTypingStats {
     pub max_wpm: f64,
     pub wpm_samples: Vec<f64>,
     pub raw_wpm_samples: Vec<f64>,
+    #[serde(default)]
+    pub corrections: usize,
 }
 
 impl From<&TypingStats> for SerializableTypingStats {
@@ -55,6 +62,7 @@ impl From<&TypingStats> for SerializableTypingStats {
             max_wpm: stats.max_wpm,
             wpm_samples: stats.wpm_samples.clone(),
             raw_wpm_samples: stats.raw_wpm_samples.clone(),
+            corrections: stats.corrections,
         }
     }
 }
@@ -72,45 +80,37 @@ impl From<SerializableTypingStats> for TypingStats {
             max_wpm: stats.max_wpm,
             wpm_samples: stats.wpm_samples,
             raw_wpm_samples: stats.raw_wpm_samples,
+            corrections: stats.corrections,
             last_sample_time: None,
         }
     }
 }
 
 impl TypingStats {
-    pub fn update(&mut self, text_source: &TextSource, typed_text: &str) {
-        let target_text = text_source.full_text();
-
-        self.correct_chars = 0;
-        self.incorrect_chars = 0;
-
-        for (i, typed_char) in typed_text.chars().enumerate() {
-            if i < target_text.len() {
-                let target_char = target_text.chars().nth(i).unwrap();
-                if typed_char == target_char {
-                    self.correct_chars += 1;
-                } else {
-                    self.incorrect_chars += 1;
-                }
-            } else {
-                self.incorrect_chars += 1;
-            }
+    pub fn add_char(&mut self, typed: char, expected: Option<char>) {
+        if Some(typed) == expected {
+            self.correct_chars += 1;
+        } else {
+            self.incorrect_chars += 1;
         }
+        self.total_chars += 1;
+        self.update_accuracy();
+    }
 
-        self.total_chars = typed_text.len();
-
-        if self.total_chars > 0 {
-            self.accuracy = (self.correct_chars as f64 / self.total_chars as f64) * 100.0;
+    pub fn remove_char(&mut self, typed: char, expected: Option<char>) {
+        if Some(typed) == expected {
+            self.correct_chars = self.correct_chars.saturating_sub(1);
         } else {
-            self.accuracy = 0.0;
+            self.incorrect_chars = self.incorrect_chars.saturating_sub(1);
         }
+        self.total_chars = self.total_chars.saturating_sub(1);
+        self.update_accuracy();
+    }
 
-        let now = Instant::now();
-        if self.last_sample_time.is_none()
-            || now
-                .duration_since(self.last_sample_time.unwrap())
-                .as_secs_f64()
-                >= 1.0
+    pub fn sample(&mut self, now: Duration) {
+        if self
+            .last_sample_time
+            .is_none_or(|last| now.saturating_sub(last).as_secs_f64() >= 1.0)
         {
             self.wpm_samples.push(self.wpm);
             self.raw_wpm_samples.push(self.raw_wpm);
@@ -118,6 +118,14 @@ impl TypingStats {
         }
     }
 
+    fn update_accuracy(&mut self) {
+        if self.total_chars > 0 {
+            self.accuracy = (self.correct_chars as f64 / self.total_chars as f64) * 100.0;
+        } else {
+            self.accuracy = 0.0;
+        }
+    }
+
     pub fn calculate_wpm(&mut self, elapsed_seconds: f64) {
         if elapsed_seconds > 0.0 {
             let minutes = elapsed_seconds / 60.0;
@@ -192,11 +200,38 @@ pub struct TestResult {
     pub accuracy: f64,
 
     pub mode: String,
+
+    #[serde(default)]
+    pub text: String,
+
+    #[serde(default)]
+    pub keystrokes: Vec<Keystroke>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum KeystrokeKind {
+    Char(char),
+
+    Backspace,
+
+    DeleteWord,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Keystroke {
+    pub elapsed_ms: u64,
+
+    pub key: KeystrokeKind,
+
+    pub cursor: usize,
 }
 
 impl TestHistory {
+    pub const MAX_RESULTS: usize = 1000;
+
+    pub const MAX_RECORDINGS: usize = 50;
+
     pub fn add_result(&mut self, result: TestResult) {
-        self.tests.push(result.clone());
         self.total_tests += 1;
         self.total_time += result.duration;
 
@@ -204,7 +239,61 @@ impl TestHistory {
             self.best_wpm = result.wpm;
         }
 
-        let total_wpm: f64 = self.tests.iter().map(|t| t.wpm).sum();
-        self.average_wpm = total_wpm / self.total_tests as f64;
+        self.average_wpm += (result.wpm - self.average_wpm) / self.total_tests as f64;
+
+        self.tests.push(result);
+        if self.tests.len() > Self::MAX_RESULTS {
+            self.tests.drain(..self.tests.len() - Self::MAX_RESULTS);
+        }
+        self.forget_old_recordings();
+    }
+
+    fn forget_old_recordings(&mut self) {
+        let mut bests: HashMap<&str, usize> = HashMap::new();
+        for (idx, test) in self.tests.iter().enumerate() {
+            let best = bests.entry(&test.mode).or_insert(idx);
+            if test.wpm >= self.tests[*best].wpm {
+                *best = idx;
+            }
+        }
+        let bests: HashSet<usize> = bests.into_values().collect();
+
+        let recent = self.tests.len().saturating_sub(Self::MAX_RECORDINGS);
+        for (idx, test) in self.tests[..recent].iter_mut().enumerate() {
+            if !bests.contains(&idx) {
+                test.text = String::new();
+                test.keystrokes = Vec::new();
+            }
+        }
+    }
+
+    pub fn personal_best(&self, mode: &str) -> Option<&TestResult> {
+        self.tests
+            .iter()
+            .filter(|t| t.mode == mode)
+            .max_by(|a, b| a.wpm.total_cmp(&b.wpm))
+    }
+
+    pub fn recent_average_wpm(&self, runs: usize) -> Option<f64> {
+        let recent: Vec<f64> = self.tests.iter().rev().take(runs).map(|t| t.wpm).collect();
+        if recent.is_empty() {
+            None
+        } else {
+            Some(recent.iter().sum::<f64>() / recent.len() as f64)
+        }
+    }
+
+    pub const STORAGE_KEY: &str = "history.json";
+
+    pub fn save_to(&self, storage: &dyn Storage) -> Result<()> {
+        let serialized = serde_json::to_string(self)?;
+        storage.write(Self::STORAGE_KEY, &serialized)
+    }
+
+    pub fn load_from(storage: &dyn Storage) -> Result<Self> {
+        match storage.read(Self::STORAGE_KEY)? {
+            Some(contents) => Ok(serde_json::from_str(&contents)?),
+            None => Ok(Self::default()),
+        }
     }
 }
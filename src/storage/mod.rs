@@ -0,0 +1,207 @@
+use anyhow::Result;
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::ffi::OsString;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+pub trait Storage: Send + Sync {
+    fn read(&self, key: &str) -> Result<Option<String>>;
+
+    fn write(&self, key: &str, contents: &str) -> Result<()>;
+}
+
+pub type SharedStorage = Arc<dyn Storage>;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileStorage {
+    dir: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStorage {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub const DIR_ENV: &str = "TUITYPE_CONFIG_DIR";
+
+    pub fn config_dir() -> Self {
+        Self::new(Self::resolve_dir(|name| std::env::var_os(name)))
+    }
+
+    pub fn resolve_dir(var: impl Fn(&str) -> Option<OsString>) -> PathBuf {
+        let var = |name| {
+            var(name)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        };
+        if let Some(dir) = var(Self::DIR_ENV) {
+            return dir;
+        }
+        let base = var("XDG_CONFIG_HOME")
+            .filter(|dir| dir.is_absolute())
+            .or_else(dirs::config_dir)
+            .unwrap_or_else(|| PathBuf::from("."));
+        base.join("tuitype")
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Storage for FileStorage {
+    fn read(&self, key: &str) -> Result<Option<String>> {
+        let path = self.dir.join(key);
+        if path.exists() {
+            Ok(Some(std::fs::read_to_string(path)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn write(&self, key: &str, contents: &str) -> Result<()> {
+        let path = self.dir.join(key);
+        std::fs::create_dir_all(path.parent().unwrap_or(&self.dir))?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub struct LocalStorage {
+    prefix: String,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    pub fn is_available() -> bool {
+        Self::storage().is_ok()
+    }
+
+    fn storage() -> Result<web_sys::Storage> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or_else(|| anyhow::anyhow!("localStorage is not available"))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Storage for LocalStorage {
+    fn read(&self, key: &str) -> Result<Option<String>> {
+        Self::storage()?
+            .get_item(&format!("{}{key}", self.prefix))
+            .map_err(|_| anyhow::anyhow!("could not read '{key}' from localStorage"))
+    }
+
+    fn write(&self, key: &str, contents: &str) -> Result<()> {
+        Self::storage()?
+            .set_item(&format!("{}{key}", self.prefix), contents)
+            .map_err(|_| anyhow::anyhow!("could not write '{key}' to localStorage"))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    entries: Mutex<HashMap<String, String>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn read(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.get(key))
+    }
+
+    fn write(&self, key: &str, contents: &str) -> Result<()> {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), contents.to_string());
+        Ok(())
+    }
+}
+
+pub struct PrefixedStorage {
+    inner: SharedStorage,
+    prefix: String,
+}
+
+impl PrefixedStorage {
+    pub fn new(inner: SharedStorage, prefix: impl Into<String>) -> Self {
+        Self {
+            inner,
+            prefix: prefix.into(),
+        }
+    }
+}
+
+impl Storage for PrefixedStorage {
+    fn read(&self, key: &str) -> Result<Option<String>> {
+        self.inner.read(&format!("{}{key}", self.prefix))
+    }
+
+    fn write(&self, key: &str, contents: &str) -> Result<()> {
+        self.inner.write(&format!("{}{key}", self.prefix), contents)
+    }
+}
+
+pub struct ReadOnlyStorage {
+    inner: SharedStorage,
+    writes: MemoryStorage,
+}
+
+impl ReadOnlyStorage {
+    pub fn new(inner: SharedStorage) -> Self {
+        Self {
+            inner,
+            writes: MemoryStorage::new(),
+        }
+    }
+}
+
+impl Storage for ReadOnlyStorage {
+    fn read(&self, key: &str) -> Result<Option<String>> {
+        match self.writes.get(key) {
+            Some(contents) => Ok(Some(contents)),
+            None => self.inner.read(key),
+        }
+    }
+
+    fn write(&self, key: &str, contents: &str) -> Result<()> {
+        self.writes.write(key, contents)
+    }
+}
+
+pub fn default_storage() -> SharedStorage {
+    #[cfg(target_arch = "wasm32")]
+    {
+        if LocalStorage::is_available() {
+            Arc::new(LocalStorage::new("tuitype/"))
+        } else {
+            Arc::new(MemoryStorage::new())
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Arc::new(FileStorage::config_dir())
+    }
+}
@@ -1,5 +1,6 @@
 use crate::config::{Config, Difficulty, TestMode};
-use rand::{seq::SliceRandom, thread_rng};
+use rand::{Rng, SeedableRng, seq::SliceRandom, thread_rng};
+use rand_chacha::ChaCha8Rng;
 
 const EASY_WORDS: &str = include_str!("word_lists/easy.txt");
 const MEDIUM_WORDS: &str = include_str!("word_lists/medium.txt");
@@ -9,15 +10,19 @@ const QUOTES: &str = include_str!("word_lists/quotes.txt");
 
 const WORDS_BATCH_SIZE: u32 = 50;
 
-const MAX_TEXT_LENGTH: usize = 800;
-
-const TARGET_TEXT_LENGTH: usize = 600;
+const LONG_QUOTE_LENGTH: usize = 300;
 
 pub struct TextSource {
     text: String,
 
+    chars: Vec<char>,
+
+    word_count: u32,
+
     difficulty: Difficulty,
 
+    word_list: Option<Vec<String>>,
+
     is_infinite: bool,
 
     pub is_scrollable: bool,
@@ -25,10 +30,21 @@ pub struct TextSource {
     total_words: u32,
 
     loaded_words: u32,
+
+    seed: u64,
+
+    rng: ChaCha8Rng,
 }
 
 impl TextSource {
     pub fn new(config: &Config) -> Self {
+        Self::with_seed(config, config.seed)
+    }
+
+    pub fn with_seed(config: &Config, seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(random_seed);
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
         if config.repeat_test && config.last_test_text.is_some() {
             let is_infinite = matches!(config.test_mode, TestMode::Timed(_));
 
@@ -38,9 +54,14 @@ impl TextSource {
                 _ => (false, 0),
             };
 
+            let text = config.last_test_text.clone().unwrap();
+
             return Self {
-                text: config.last_test_text.clone().unwrap(),
+                chars: text.chars().collect(),
+                word_count: text.split_whitespace().count() as u32,
+                text,
                 difficulty: config.difficulty,
+                word_list: config.word_list.clone(),
                 is_infinite,
                 is_scrollable,
                 total_words,
@@ -49,31 +70,50 @@ impl TextSource {
                 } else {
                     total_words
                 },
+                seed,
+                rng,
             };
         }
 
         let is_infinite = matches!(config.test_mode, TestMode::Timed(_));
 
-        let (is_scrollable, total_words, initial_words) = match config.test_mode {
-            TestMode::Words(count) if count > WORDS_BATCH_SIZE => (true, count, WORDS_BATCH_SIZE),
-            TestMode::Words(count) => (false, count, count),
-            TestMode::Quote => {
-                let quote = Self::get_random_quote();
+        let quote =
+            matches!(config.test_mode, TestMode::Quote).then(|| Self::get_random_quote(&mut rng));
+
+        let (is_scrollable, total_words, initial_words) = match (config.test_mode, &quote) {
+            (TestMode::Words(count), _) if count > WORDS_BATCH_SIZE => {
+                (true, count, WORDS_BATCH_SIZE)
+            }
+            (TestMode::Words(count), _) => (false, count, count),
+            (TestMode::Quote, Some(quote)) => {
                 let word_count = quote.split_whitespace().count() as u32;
 
-                let is_long_quote = quote.len() > TARGET_TEXT_LENGTH / 2;
+                let is_long_quote = quote.len() > LONG_QUOTE_LENGTH;
                 (is_long_quote, word_count, word_count)
             }
             _ => (false, 0, 0),
         };
 
         let text = match config.test_mode {
-            TestMode::Words(_count) if is_scrollable => {
-                Self::generate_words(config.difficulty, initial_words)
-            }
-            TestMode::Words(count) => Self::generate_words(config.difficulty, count),
-            TestMode::Timed(_) => Self::generate_words(config.difficulty, WORDS_BATCH_SIZE),
-            TestMode::Quote => Self::get_random_quote(),
+            TestMode::Words(_count) if is_scrollable => Self::generate_words(
+                &mut rng,
+                config.difficulty,
+                config.word_list.as_deref(),
+                initial_words,
+            ),
+            TestMode::Words(count) => Self::generate_words(
+                &mut rng,
+                config.difficulty,
+                config.word_list.as_deref(),
+                count,
+            ),
+            TestMode::Timed(_) => Self::generate_words(
+                &mut rng,
+                config.difficulty,
+                config.word_list.as_deref(),
+                WORDS_BATCH_SIZE,
+            ),
+            TestMode::Quote => quote.unwrap_or_default(),
             TestMode::Custom => config
                 .custom_text
                 .clone()
@@ -81,15 +121,24 @@ impl TextSource {
         };
 
         Self {
+            chars: text.chars().collect(),
+            word_count: text.split_whitespace().count() as u32,
             text,
             difficulty: config.difficulty,
+            word_list: config.word_list.clone(),
             is_infinite,
             is_scrollable,
             total_words,
             loaded_words: initial_words,
+            seed,
+            rng,
         }
     }
 
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
     pub fn should_add_more_words(&self, cursor_pos: usize) -> bool {
         if !self.is_infinite && !self.is_scrollable {
             return false;
@@ -99,18 +148,18 @@ impl TextSource {
             return false;
         }
 
-        let cursor_threshold = self.text.len() * 3 / 4;
+        let cursor_threshold = self.chars.len() * 3 / 4;
 
         cursor_pos > cursor_threshold
     }
 
-    pub fn add_more_words(&mut self) -> usize {
+    pub fn add_more_words(&mut self) {
         if !self.is_infinite && !self.is_scrollable {
-            return 0;
+            return;
         }
 
         if self.is_scrollable && self.loaded_words >= self.total_words {
-            return 0;
+            return;
         }
 
         let words_to_add = if self.is_scrollable {
@@ -122,63 +171,59 @@ impl TextSource {
         };
 
         let additional_words = match self.difficulty {
-            Difficulty::Custom if self.is_infinite => {
-                Self::generate_words(Difficulty::Medium, words_to_add)
-            }
-            _ => Self::generate_words(self.difficulty, words_to_add),
+            Difficulty::Custom if self.is_infinite => Self::generate_words(
+                &mut self.rng,
+                Difficulty::Medium,
+                self.word_list.as_deref(),
+                words_to_add,
+            ),
+            _ => Self::generate_words(
+                &mut self.rng,
+                self.difficulty,
+                self.word_list.as_deref(),
+                words_to_add,
+            ),
         };
 
         if !self.text.is_empty() {
             self.text.push(' ');
+            self.chars.push(' ');
         }
 
         self.text.push_str(&additional_words);
+        self.chars.extend(additional_words.chars());
+        self.word_count += additional_words.split_whitespace().count() as u32;
 
         if self.is_scrollable {
             self.loaded_words += words_to_add;
         }
+    }
 
-        let mut trimmed_count = 0;
-
-        if self.text.len() > MAX_TEXT_LENGTH {
-            let text_to_keep = TARGET_TEXT_LENGTH;
-
-            let trim_start_pos = self.text.len() - text_to_keep;
-
-            if trim_start_pos > 0 && self.text.len() > text_to_keep {
-                if let Some(space_pos) = self.text[trim_start_pos..].find(' ') {
-                    let trim_pos = trim_start_pos + space_pos + 1;
+    pub fn full_text(&self) -> &str {
+        &self.text
+    }
 
-                    if trim_pos < self.text.len() {
-                        trimmed_count = trim_pos;
-                        self.text = self.text[trim_pos..].to_string();
-                    }
-                } else {
-                    trimmed_count = self.text.len() - text_to_keep;
-                    self.text = self.text[trimmed_count..].to_string();
-                }
-            }
-        }
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
 
-        trimmed_count
+    pub fn char_at(&self, pos: usize) -> Option<char> {
+        self.chars.get(pos).copied()
     }
 
-    pub fn save_to_config(&self, config: &mut Config) {
-        if config.repeat_test {
-            config.last_test_text = Some(self.text.clone());
-            config.save().ok();
-        }
+    pub fn len(&self) -> usize {
+        self.chars.len()
     }
 
-    pub fn full_text(&self) -> &str {
-        &self.text
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
     }
 
     pub fn total_words(&self) -> u32 {
         if self.is_scrollable {
             self.total_words
         } else {
-            self.text.split_whitespace().count() as u32
+            self.word_count
         }
     }
 
@@ -188,28 +233,37 @@ impl TextSource {
         } else {
             match self.difficulty {
                 Difficulty::Custom => true,
-                _ => self.text.split_whitespace().count() as u32 == self.total_words,
+                _ => self.word_count == self.total_words,
             }
         }
     }
 
-    fn generate_words(difficulty: Difficulty, count: u32) -> String {
-        let word_list = match difficulty {
-            Difficulty::Easy => EASY_WORDS,
-            Difficulty::Medium => MEDIUM_WORDS,
-            Difficulty::Hard => HARD_WORDS,
-            Difficulty::Custom => MEDIUM_WORDS,
+    fn generate_words(
+        rng: &mut ChaCha8Rng,
+        difficulty: Difficulty,
+        word_list: Option<&[String]>,
+        count: u32,
+    ) -> String {
+        let words: Vec<&str> = match word_list {
+            Some(word_list) if !word_list.is_empty() => {
+                word_list.iter().map(String::as_str).collect()
+            }
+            _ => match difficulty {
+                Difficulty::Easy => EASY_WORDS,
+                Difficulty::Medium => MEDIUM_WORDS,
+                Difficulty::Hard => HARD_WORDS,
+                Difficulty::Custom => MEDIUM_WORDS,
+            }
+            .lines()
+            .collect(),
         };
 
-        let words: Vec<&str> = word_list.lines().collect();
-        let mut rng = thread_rng();
-
         let mut result = String::new();
         for i in 0..count {
             if i > 0 {
                 result.push(' ');
             }
-            if let Some(word) = words.choose(&mut rng) {
+            if let Some(word) = words.choose(rng) {
                 result.push_str(word);
             }
         }
@@ -217,11 +271,10 @@ impl TextSource {
         result
     }
 
-    fn get_random_quote() -> String {
+    fn get_random_quote(rng: &mut ChaCha8Rng) -> String {
         let quotes: Vec<&str> = QUOTES.split("\n\n").collect();
-        let mut rng = thread_rng();
 
-        if let Some(quote) = quotes.choose(&mut rng) {
+        if let Some(quote) = quotes.choose(rng) {
             quote
                 .trim()
                 .lines()
@@ -232,28 +285,8 @@ impl TextSource {
             "The quick brown fox jumps over the lazy dog.".to_string()
         }
     }
+}
 
-    pub fn trim_text_if_needed(&mut self, cursor_pos: usize) -> usize {
-        if !self.is_scrollable || cursor_pos < self.text.len() / 2 {
-            return 0;
-        }
-
-        let mut trimmed_count = 0;
-        if self.text.len() > MAX_TEXT_LENGTH {
-            let trim_start_pos = cursor_pos.saturating_sub(TARGET_TEXT_LENGTH / 4);
-
-            if trim_start_pos > 0 {
-                if let Some(space_pos) = self.text[trim_start_pos..].find(' ') {
-                    let trim_pos = trim_start_pos + space_pos + 1;
-
-                    if trim_pos < self.text.len() {
-                        trimmed_count = trim_pos;
-                        self.text = self.text[trim_pos..].to_string();
-                    }
-                }
-            }
-        }
-
-        trimmed_count
-    }
+pub fn random_seed() -> u64 {
+    thread_rng().r#gen()
 }
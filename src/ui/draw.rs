@@ -1,12 +1,24 @@
-use crate::ui::{App, MenuState, WarningState};
+use crate::config::{
+    CaretStyle, FontStyle, KeyChord, backspace_mode_name, caret_style_name, font_style_name,
+    key_debounce_name, pace_caret_name,
+};
+use crate::net::{PlayerState, Race, RacePhase};
+use crate::replay::Replay;
+use crate::ui::glyphs::{GLYPH_HEIGHT, GLYPH_WIDTH, glyph};
+use crate::ui::layout::{VISIBLE_LINES, cell_width, visible_line_range, wrap_words};
+use crate::ui::{App, HitTarget, MenuState, WarningState};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     symbols,
-    text::{Line, Span, Text},
-    widgets::{Block, BorderType, Borders, Chart, Dataset, Gauge, Paragraph, Wrap},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Chart, Clear, Dataset, Gauge, Paragraph, Wrap},
 };
+use std::borrow::Cow;
+
+const NBSP: &str = "\u{00a0}";
+const BAR_CARET: &str = "\u{258f}";
 
 pub fn render(app: &App, frame: &mut Frame) -> anyhow::Result<()> {
     let min_width = 82;
@@ -14,6 +26,8 @@ pub fn render(app: &App, frame: &mut Frame) -> anyhow::Result<()> {
 
     let current_size = frame.size();
 
+    app.hit_regions.borrow_mut().clear();
+
     if current_size.width < min_width || current_size.height < min_height {
         let message = format!(
             "Terminal too small\nMinimum size: {}x{}\nCurrent size: {}x{}",
@@ -26,24 +40,51 @@ pub fn render(app: &App, frame: &mut Frame) -> anyhow::Result<()> {
         return Ok(());
     }
 
-    let stats_height = if current_size.height < 15 { 3 } else { 6 };
+    let stats_height = match (app.show_stats, current_size.height) {
+        (false, _) => 0,
+        (true, h) if h < 15 => 3,
+        (true, _) => 6,
+    };
+
+    let notice_height = u16::from(app.notice().is_some());
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(5), Constraint::Length(stats_height)])
+        .constraints([
+            Constraint::Min(5),
+            Constraint::Length(notice_height),
+            Constraint::Length(stats_height),
+        ])
         .split(frame.size());
 
     if app.warning_state != WarningState::None {
         draw_warning(app, frame, chunks[0]);
+    } else if let Some(race) = &app.race
+        && matches!(app.menu_state, MenuState::Typing | MenuState::TestComplete)
+    {
+        draw_race(app, race, frame, chunks[0]);
     } else if app.menu_state == MenuState::TestComplete {
         draw_test_complete(app, frame, chunks[0]);
+    } else if matches!(app.menu_state, MenuState::Replay(_)) {
+        draw_replay(app, frame, chunks[0]);
+    } else if matches!(app.menu_state, MenuState::CommandPalette { .. }) {
+        draw_command_palette(app, frame, chunks[0]);
     } else if app.menu_state != MenuState::Typing {
         draw_menu(app, frame, chunks[0]);
     } else {
         draw_typing_area(app, frame, chunks[0]);
     }
 
-    draw_stats(app, frame, chunks[1]);
+    if let Some(notice) = app.notice() {
+        let paragraph = Paragraph::new(notice)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Yellow));
+        frame.render_widget(paragraph, chunks[1]);
+    }
+
+    if app.show_stats {
+        draw_stats(app, frame, chunks[2]);
+    }
 
     Ok(())
 }
@@ -95,7 +136,7 @@ fn draw_typing_area(app: &App, frame: &mut Frame, area: Rect) {
         }
     );
 
-    let time_remaining_str = if let Some(remaining) = app.time_remaining {
+    let time_remaining_str = if let Some(remaining) = app.session.time_remaining {
         match app.config.test_mode {
             crate::config::TestMode::Timed(_) => format!("Time: {remaining}s"),
             _ => String::new(),
@@ -107,18 +148,23 @@ fn draw_typing_area(app: &App, frame: &mut Frame, area: Rect) {
         }
     };
 
-    let stats_str = format!(
+    let mut stats_str = format!(
         "WPM: {:.1} | Raw WPM: {:.1} | Acc: {:.1}%",
-        app.stats.wpm, app.stats.raw_wpm, app.stats.accuracy
+        app.session.stats.wpm, app.session.stats.raw_wpm, app.session.stats.accuracy
     );
+    if app.missing_personal_best() {
+        stats_str.push_str(" | Pace: no PB yet");
+    }
+
+    let menu_key = key_hint(app.config.keybindings.open_menu);
 
     let single_line = if !time_remaining_str.is_empty() {
         format!(
-            "{app_title} | {test_mode_str} | {diff_str} | {repeat_mode_str} | {end_on_error_str} | {stats_str} | {time_remaining_str} | Press ESC for menu"
+            "{app_title} | {test_mode_str} | {diff_str} | {repeat_mode_str} | {end_on_error_str} | {stats_str} | {time_remaining_str} | Press {menu_key} for menu"
         )
     } else {
         format!(
-            "{app_title} | {test_mode_str} | {diff_str} | {repeat_mode_str} | {end_on_error_str} | {stats_str} | Press ESC for menu"
+            "{app_title} | {test_mode_str} | {diff_str} | {repeat_mode_str} | {end_on_error_str} | {stats_str} | Press {menu_key} for menu"
         )
     };
 
@@ -144,18 +190,18 @@ fn draw_typing_area(app: &App, frame: &mut Frame, area: Rect) {
         let second_line;
 
         if area.width < 40 {
-            let esc_menu_text = "ESC:Menu";
+            let esc_menu_text = format!("{menu_key}:Menu");
             let show_time = !time_remaining_str.is_empty();
 
             if show_time {
                 first_line = format!("{app_title} | {time_remaining_str}");
-                second_line = format!("WPM: {:.1} | {}", app.stats.wpm, esc_menu_text);
+                second_line = format!("WPM: {:.1} | {}", app.session.stats.wpm, esc_menu_text);
             } else {
                 first_line = app_title.to_string();
-                second_line = format!("WPM: {:.1} | {}", app.stats.wpm, esc_menu_text);
+                second_line = format!("WPM: {:.1} | {}", app.session.stats.wpm, esc_menu_text);
             }
         } else if area.width < 60 {
-            let esc_menu_text = "Press ESC for menu";
+            let esc_menu_text = format!("Press {menu_key} for menu");
             let show_time = !time_remaining_str.is_empty();
 
             if show_time {
@@ -166,7 +212,7 @@ fn draw_typing_area(app: &App, frame: &mut Frame, area: Rect) {
 
             second_line = format!(
                 "WPM: {:.1} | Raw: {:.1} | {}",
-                app.stats.wpm, app.stats.raw_wpm, esc_menu_text
+                app.session.stats.wpm, app.session.stats.raw_wpm, esc_menu_text
             );
         } else {
             let first_row_with_config = if area.width <= 90 {
@@ -186,21 +232,21 @@ fn draw_typing_area(app: &App, frame: &mut Frame, area: Rect) {
             if area.width <= 90 {
                 first_line = format!("{first_row_with_time} | {stats_str}");
                 second_line = format!(
-                    "{diff_str} | {repeat_mode_str} | {end_on_error_str} | Press ESC for menu"
+                    "{diff_str} | {repeat_mode_str} | {end_on_error_str} | Press {menu_key} for menu"
                 );
             } else if first_row_with_time.chars().count() + stats_str.chars().count() + 3
                 <= width_available
             {
                 first_line = format!("{first_row_with_time} | {stats_str}");
-                second_line = "Press ESC for menu".to_string();
+                second_line = format!("Press {menu_key} for menu");
             } else if first_row_with_config.chars().count() + time_remaining_str.chars().count() + 3
                 <= width_available
             {
                 first_line = first_row_with_time;
-                second_line = format!("{stats_str} | Press ESC for menu");
+                second_line = format!("{stats_str} | Press {menu_key} for menu");
             } else {
                 first_line = first_row_with_config;
-                second_line = format!("{stats_str} | Press ESC for menu");
+                second_line = format!("{stats_str} | Press {menu_key} for menu");
             }
         }
 
@@ -277,41 +323,75 @@ fn draw_typing_area(app: &App, frame: &mut Frame, area: Rect) {
     }
 }
 
+struct TextView<'a> {
+    target: &'a [char],
+    typed: &'a [char],
+    cursor_pos: usize,
+    pace_pos: Option<usize>,
+}
+
 fn render_typing_text(app: &App, frame: &mut Frame, typing_area: Rect) {
-    let target_text = app.text_source.full_text();
-
-    let is_quote_mode = matches!(app.config.test_mode, crate::config::TestMode::Quote);
-    let display_window_size = typing_area.width as usize * 4;
-
-    let (start_pos, target_display_text) =
-        if is_quote_mode && target_text.len() > display_window_size {
-            let visible_start = if app.cursor_pos > display_window_size / 2 {
-                let ideal_start = app.cursor_pos - display_window_size / 2;
-                if ideal_start > 0 {
-                    match target_text[..ideal_start].rfind(' ') {
-                        Some(pos) => pos + 1,
-                        None => 0,
-                    }
-                } else {
-                    0
-                }
-            } else {
-                0
-            };
+    let view = TextView {
+        target: app.session.text_source.chars(),
+        typed: &app.session.typed_chars,
+        cursor_pos: app.session.cursor_pos,
+        pace_pos: app.pace_pos,
+    };
+    render_text_view(app, &view, frame, typing_area);
+}
+
+fn render_text_view(app: &App, view: &TextView, frame: &mut Frame, typing_area: Rect) {
+    let typing_area = if app.config.font_style == FontStyle::Large
+        && typing_area.height as usize >= GLYPH_HEIGHT + 3
+    {
+        let big_word_area = Rect::new(
+            typing_area.x,
+            typing_area.y,
+            typing_area.width,
+            GLYPH_HEIGHT as u16 + 1,
+        );
+        draw_large_word(app, view, frame, big_word_area);
 
-            let visible_end = (visible_start + display_window_size).min(target_text.len());
-            (visible_start, &target_text[visible_start..visible_end])
+        Rect::new(
+            typing_area.x,
+            typing_area.y + GLYPH_HEIGHT as u16 + 1,
+            typing_area.width,
+            typing_area.height - GLYPH_HEIGHT as u16 - 1,
+        )
+    } else {
+        typing_area
+    };
+
+    let target_chars = view.target;
+    let typed_chars = view.typed;
+
+    let display_chars: Cow<[char]> =
+        if typed_chars.len() > target_chars.len() || view.cursor_pos >= target_chars.len() {
+            let mut display_chars = target_chars.to_vec();
+            if typed_chars.len() > target_chars.len() {
+                display_chars.extend_from_slice(&typed_chars[target_chars.len()..]);
+            }
+            if view.cursor_pos >= display_chars.len() {
+                display_chars.push(' ');
+            }
+            Cow::Owned(display_chars)
         } else {
-            (0, target_text)
+            Cow::Borrowed(target_chars)
         };
 
-    let mut styled_spans = Vec::new();
-
-    let correct_style = Style::default().fg(Color::Rgb(
-        app.theme.correct.0,
-        app.theme.correct.1,
-        app.theme.correct.2,
-    ));
+    let correct_style = if app.config.font_style == FontStyle::Simple {
+        Style::default().fg(Color::Rgb(
+            app.theme.text.0,
+            app.theme.text.1,
+            app.theme.text.2,
+        ))
+    } else {
+        Style::default().fg(Color::Rgb(
+            app.theme.correct.0,
+            app.theme.correct.1,
+            app.theme.correct.2,
+        ))
+    };
 
     let incorrect_style = Style::default().fg(Color::Rgb(
         app.theme.incorrect.0,
@@ -325,77 +405,171 @@ fn render_typing_text(app: &App, frame: &mut Frame, typing_area: Rect) {
         app.theme.pending.2,
     ));
 
-    for (i, ch) in target_display_text.chars().enumerate() {
-        let absolute_pos = start_pos + i;
-        let span = if absolute_pos < app.typed_text.len() {
-            let typed_char = app.typed_text.chars().nth(absolute_pos).unwrap();
-            if typed_char == ch {
-                Span::styled(ch.to_string(), correct_style)
-            } else {
-                Span::styled(ch.to_string(), incorrect_style)
-            }
-        } else if absolute_pos == app.cursor_pos {
-            Span::styled(
-                ch.to_string(),
-                Style::default()
-                    .fg(Color::Rgb(
-                        app.theme.cursor.0,
-                        app.theme.cursor.1,
-                        app.theme.cursor.2,
-                    ))
-                    .add_modifier(Modifier::REVERSED),
-            )
-        } else {
-            Span::styled(ch.to_string(), pending_style)
-        };
-
-        styled_spans.push(span);
-    }
+    let cursor_color = Color::Rgb(app.theme.cursor.0, app.theme.cursor.1, app.theme.cursor.2);
 
-    if app.typed_text.len() > target_text.len() {
-        for (i, ch) in app.typed_text.chars().skip(target_text.len()).enumerate() {
-            let pos = target_text.len() + i;
-            let span = if pos == app.cursor_pos {
-                Span::styled(
-                    ch.to_string(),
-                    Style::default()
-                        .fg(Color::Rgb(
-                            app.theme.incorrect.0,
-                            app.theme.incorrect.1,
-                            app.theme.incorrect.2,
-                        ))
-                        .add_modifier(Modifier::REVERSED),
-                )
-            } else {
-                Span::styled(ch.to_string(), incorrect_style)
-            };
-            styled_spans.push(span);
+    let cursor_style = if app.config.use_terminal_cursor {
+        pending_style
+    } else {
+        match app.config.caret_style {
+            CaretStyle::Block => Style::default()
+                .fg(cursor_color)
+                .add_modifier(Modifier::REVERSED),
+            CaretStyle::Underline => Style::default()
+                .fg(cursor_color)
+                .add_modifier(Modifier::UNDERLINED),
+            CaretStyle::Bar | CaretStyle::Off => pending_style,
         }
-    }
+    };
 
-    if app.cursor_pos >= app.typed_text.len() && app.cursor_pos >= target_text.len() {
-        styled_spans.push(Span::styled(
-            " ",
-            Style::default()
-                .fg(Color::Rgb(
-                    app.theme.cursor.0,
-                    app.theme.cursor.1,
-                    app.theme.cursor.2,
-                ))
-                .add_modifier(Modifier::REVERSED),
+    let pace_style = Style::default()
+        .fg(Color::Rgb(
+            app.theme.background.0,
+            app.theme.background.1,
+            app.theme.background.2,
+        ))
+        .bg(Color::Rgb(
+            app.theme.accent.0,
+            app.theme.accent.1,
+            app.theme.accent.2,
         ));
+
+    let letter_spacing = app.config.font_style == FontStyle::Monospace;
+    let bar_caret = app.config.caret_style == CaretStyle::Bar && !app.config.use_terminal_cursor;
+    let wrap_width = (typing_area.width as usize).saturating_sub(usize::from(bar_caret));
+
+    let line_ranges = wrap_words(&display_chars, wrap_width, letter_spacing);
+    let active_line = line_ranges
+        .iter()
+        .position(|range| range.contains(&view.cursor_pos))
+        .unwrap_or(line_ranges.len() - 1);
+    let visible = visible_line_range(
+        line_ranges.len(),
+        active_line,
+        VISIBLE_LINES.min(typing_area.height as usize),
+    );
+
+    if app.config.use_terminal_cursor && app.config.caret_style != CaretStyle::Off {
+        let caret_line = &line_ranges[active_line];
+        let column: usize = display_chars[caret_line.start..view.cursor_pos.max(caret_line.start)]
+            .iter()
+            .map(|&c| cell_width(c, letter_spacing))
+            .sum();
+        let row = active_line - visible.start;
+
+        if row < typing_area.height as usize && column < typing_area.width as usize {
+            frame.set_cursor(typing_area.x + column as u16, typing_area.y + row as u16);
+        }
     }
 
-    let text = Text::from(Line::from(styled_spans));
+    let lines: Vec<Line> = line_ranges[visible]
+        .iter()
+        .map(|range| {
+            let mut spans = Vec::new();
+
+            for pos in range.clone() {
+                let ch = display_chars[pos];
+                let style = if pos < typed_chars.len() {
+                    let is_correct = pos < target_chars.len() && typed_chars[pos] == ch;
+                    if is_correct {
+                        correct_style
+                    } else {
+                        incorrect_style
+                    }
+                } else if pos == view.cursor_pos {
+                    cursor_style
+                } else {
+                    pending_style
+                };
+                let style = if Some(pos) == view.pace_pos && pos != view.cursor_pos {
+                    pace_style
+                } else {
+                    style
+                };
+
+                if bar_caret && pos == view.cursor_pos {
+                    spans.push(Span::styled(BAR_CARET, Style::default().fg(cursor_color)));
+                }
+                spans.push(Span::styled(ch.to_string(), style));
+
+                if letter_spacing && ch != ' ' {
+                    spans.push(Span::styled(NBSP, pending_style));
+                }
+            }
+
+            Line::from(spans)
+        })
+        .collect();
 
-    let paragraph = Paragraph::new(text)
+    let paragraph = Paragraph::new(lines)
         .block(Block::default())
-        .wrap(Wrap { trim: true })
         .alignment(Alignment::Left);
 
     frame.render_widget(paragraph, typing_area);
 }
 
+fn draw_large_word(app: &App, view: &TextView, frame: &mut Frame, area: Rect) {
+    let target_chars = view.target;
+    let typed_chars = view.typed;
+
+    let anchor = view.cursor_pos.min(target_chars.len());
+    let at_word_end = anchor >= target_chars.len() || target_chars[anchor] == ' ';
+    let word_end = if at_word_end {
+        anchor
+    } else {
+        target_chars[anchor..]
+            .iter()
+            .position(|&c| c == ' ')
+            .map(|i| anchor + i)
+            .unwrap_or(target_chars.len())
+    };
+    let word_start = target_chars[..word_end]
+        .iter()
+        .rposition(|&c| c == ' ')
+        .map(|i| i + 1)
+        .unwrap_or(0);
+
+    let max_glyphs = area.width as usize / (GLYPH_WIDTH + 1);
+    let word_end = word_end.min(word_start + max_glyphs);
+
+    let glyph_style = |pos: usize| {
+        let color = if pos < typed_chars.len() {
+            if typed_chars[pos] == target_chars[pos] {
+                if app.config.font_style == FontStyle::Simple {
+                    app.theme.text
+                } else {
+                    app.theme.correct
+                }
+            } else {
+                app.theme.incorrect
+            }
+        } else if pos == view.cursor_pos {
+            app.theme.cursor
+        } else {
+            app.theme.pending
+        };
+        Style::default().fg(Color::Rgb(color.0, color.1, color.2))
+    };
+
+    let lines: Vec<Line> = (0..GLYPH_HEIGHT)
+        .map(|row| {
+            let spans: Vec<Span> = (word_start..word_end)
+                .map(|pos| {
+                    let pixels: String = glyph(target_chars[pos])[row]
+                        .chars()
+                        .map(|p| if p == '#' { '█' } else { ' ' })
+                        .collect();
+                    Span::styled(format!("{pixels} "), glyph_style(pos))
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(lines).alignment(Alignment::Center);
+
+    frame.render_widget(paragraph, area);
+}
+
 fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
     let app_title = format!(
         "TuiType{}",
@@ -406,11 +580,14 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
         }
     );
 
+    let next_key = key_hint(app.config.keybindings.next_test);
+    let repeat_key = key_hint(app.config.keybindings.repeat_text);
+
     let width = area.width.saturating_sub(10).clamp(20, 60).min(area.width);
     let height = area.height.saturating_sub(2).clamp(3, 15).min(area.height);
 
     if width < 15 || height < 3 {
-        let text = "Test Complete\nPress ENTER to restart";
+        let text = format!("Test Complete\nPress {next_key} to restart");
         let paragraph = Paragraph::new(text)
             .alignment(Alignment::Center)
             .style(Style::default().fg(Color::White));
@@ -429,14 +606,14 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
 
         let results = format!(
             "WPM: {:.1} | Raw WPM: {:.1} | Acc: {:.1}%",
-            app.stats.wpm, app.stats.raw_wpm, app.stats.accuracy
+            app.session.stats.wpm, app.session.stats.raw_wpm, app.session.stats.accuracy
         );
         let paragraph = Paragraph::new(vec![
             Line::from(vec![Span::styled(
                 results,
                 Style::default().add_modifier(Modifier::BOLD),
             )]),
-            Line::from("Press ENTER to restart"),
+            Line::from(format!("Press {next_key} to restart")),
         ])
         .alignment(Alignment::Center);
 
@@ -464,8 +641,9 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
 
     frame.render_widget(block.clone(), popup_area);
 
-    let duration = if let (Some(start), Some(end)) = (app.start_time, app.end_time) {
-        end.duration_since(start).as_secs_f64()
+    let duration = if let (Some(start), Some(end)) = (app.session.start_time, app.session.end_time)
+    {
+        end.saturating_sub(start).as_secs_f64()
     } else {
         0.0
     };
@@ -490,7 +668,7 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
         vec![Line::from(vec![Span::styled(
             format!(
                 "WPM: {:.1} | Raw WPM: {:.1} | Acc: {:.1}%",
-                app.stats.wpm, app.stats.raw_wpm, app.stats.accuracy
+                app.session.stats.wpm, app.session.stats.raw_wpm, app.session.stats.accuracy
             ),
             Style::default().add_modifier(Modifier::BOLD),
         )])]
@@ -504,21 +682,28 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
             Line::from(vec![
                 Span::raw("WPM: "),
                 Span::styled(
-                    format!("{:.1}", app.stats.wpm),
+                    format!("{:.1}", app.session.stats.wpm),
                     Style::default().add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(vec![
                 Span::raw("Raw WPM: "),
                 Span::styled(
-                    format!("{:.1}", app.stats.raw_wpm),
+                    format!("{:.1}", app.session.stats.raw_wpm),
                     Style::default().add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(vec![
                 Span::raw("Accuracy: "),
                 Span::styled(
-                    format!("{:.1}%", app.stats.accuracy),
+                    format!("{:.1}%", app.session.stats.accuracy),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::from(vec![
+                Span::raw("Corrections: "),
+                Span::styled(
+                    app.session.stats.corrections.to_string(),
                     Style::default().add_modifier(Modifier::BOLD),
                 ),
             ]),
@@ -532,7 +717,19 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
         ]
     };
 
-    if let Some(reason) = &app.test_end_reason {
+    if app.session.dropped_keys > 0 && inner_area.height >= 8 {
+        results_lines.push(Line::from(vec![
+            Span::raw("Dropped Keys: "),
+            Span::styled(
+                app.session.dropped_keys.to_string(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+
+    if let Some(reason) = &app.session.test_end_reason {
         results_lines.push(Line::default());
         results_lines.push(Line::from(vec![
             Span::raw("Note: "),
@@ -557,7 +754,9 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
         crate::config::Difficulty::Custom => "Custom",
     };
 
-    let settings_lines = vec![
+    let has_seed = app.text_is_seeded();
+
+    let mut settings_lines = vec![
         Line::from(vec![Span::styled(
             "TEST SETTINGS",
             Style::default().add_modifier(Modifier::BOLD),
@@ -592,9 +791,29 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
                 Style::default().add_modifier(Modifier::BOLD),
             ),
         ]),
-        Line::default(),
     ];
 
+    if has_seed {
+        settings_lines.push(Line::from(vec![
+            Span::raw("Seed: "),
+            Span::styled(
+                app.session.text_source.seed().to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+    settings_lines.push(Line::default());
+
+    let retry_note = Line::from(vec![Span::styled(
+        format!("Press {repeat_key} to retry this seed"),
+        Style::default().fg(Color::Rgb(
+            app.theme.text.0,
+            app.theme.text.1,
+            app.theme.text.2,
+        )),
+    )])
+    .alignment(Alignment::Center);
+
     let divider = Block::default()
         .borders(Borders::LEFT)
         .border_style(Style::default().fg(Color::White));
@@ -612,12 +831,12 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
             combined_lines.push(Line::from(vec![Span::styled(
                 format!(
                     "WPM: {:.1} | Raw WPM: {:.1} | Acc: {:.1}%",
-                    app.stats.wpm, app.stats.raw_wpm, app.stats.accuracy
+                    app.session.stats.wpm, app.session.stats.raw_wpm, app.session.stats.accuracy
                 ),
                 Style::default().add_modifier(Modifier::BOLD),
             )]));
 
-            if let Some(reason) = &app.test_end_reason {
+            if let Some(reason) = &app.session.test_end_reason {
                 combined_lines.push(Line::from(vec![Span::styled(
                     reason,
                     Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
@@ -625,7 +844,7 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
             }
 
             combined_lines.push(Line::from(vec![Span::styled(
-                "Press ENTER to restart",
+                format!("Press {next_key} to restart"),
                 Style::default().fg(Color::Rgb(
                     app.theme.text.0,
                     app.theme.text.1,
@@ -637,6 +856,10 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
             combined_lines.push(Line::default());
             combined_lines.push(Line::default());
             combined_lines.extend_from_slice(&settings_lines);
+
+            if has_seed {
+                combined_lines.push(retry_note);
+            }
         }
 
         let content_height = combined_lines.len() as u16;
@@ -667,7 +890,7 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
                 let note_area = Rect::new(columns[0].x, note_y, columns[0].width, 1);
 
                 let restart_note = Line::from(vec![Span::styled(
-                    "Press ENTER to restart typing test",
+                    format!("Press {next_key} to restart typing test"),
                     Style::default().fg(Color::Rgb(
                         app.theme.text.0,
                         app.theme.text.1,
@@ -681,6 +904,9 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
                     .style(Style::default().fg(Color::White));
 
                 frame.render_widget(note_paragraph, note_area);
+                app.hit_regions
+                    .borrow_mut()
+                    .push(note_area, HitTarget::Restart);
             }
         }
     } else {
@@ -721,7 +947,7 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
 
         if columns[0].height > content_height + padding_top + 2 {
             let restart_note = Line::from(vec![Span::styled(
-                "Press ENTER to restart typing test",
+                format!("Press {next_key} to restart typing test"),
                 Style::default().fg(Color::Rgb(
                     app.theme.text.0,
                     app.theme.text.1,
@@ -742,6 +968,27 @@ fn draw_test_complete_new(app: &App, frame: &mut Frame, area: Rect) {
                 .style(Style::default().fg(Color::White));
 
             frame.render_widget(note_paragraph, note_area);
+            app.hit_regions
+                .borrow_mut()
+                .push(note_area, HitTarget::Restart);
+
+            if has_seed {
+                let retry_area = Rect::new(
+                    columns[1].x + 1,
+                    note_area.y,
+                    columns[1].width.saturating_sub(1),
+                    1,
+                );
+
+                let retry_paragraph = Paragraph::new(vec![retry_note])
+                    .alignment(Alignment::Center)
+                    .style(Style::default().fg(Color::White));
+
+                frame.render_widget(retry_paragraph, retry_area);
+                app.hit_regions
+                    .borrow_mut()
+                    .push(retry_area, HitTarget::RetrySeed);
+            }
         }
     }
 }
@@ -778,7 +1025,7 @@ fn draw_stats(app: &App, frame: &mut Frame, area: Rect) {
 fn draw_compact_stats(app: &App, frame: &mut Frame, area: Rect) {
     let label = format!(
         "WPM: {:.0} Raw WPM: {:.0} Acc: {:.0}%",
-        app.stats.wpm, app.stats.raw_wpm, app.stats.accuracy
+        app.session.stats.wpm, app.session.stats.raw_wpm, app.session.stats.accuracy
     );
     let paragraph = Paragraph::new(label)
         .style(Style::default().fg(Color::Rgb(
@@ -792,8 +1039,8 @@ fn draw_compact_stats(app: &App, frame: &mut Frame, area: Rect) {
 }
 
 fn draw_minimal_gauge(app: &App, frame: &mut Frame, area: Rect) {
-    let accuracy_value = (app.stats.accuracy.clamp(0.0, 100.0) as u16).min(100);
-    let accuracy_label = format!("{:.1}%", app.stats.accuracy);
+    let accuracy_value = (app.session.stats.accuracy.clamp(0.0, 100.0) as u16).min(100);
+    let accuracy_label = format!("{:.1}%", app.session.stats.accuracy);
 
     let accuracy_gauge = Gauge::default()
         .block(Block::default().borders(Borders::ALL).title("Acc"))
@@ -813,10 +1060,10 @@ fn draw_gauges(app: &App, frame: &mut Frame, area: Rect) {
         return;
     }
 
-    let accuracy_value = (app.stats.accuracy.clamp(0.0, 100.0) as u16).min(100);
+    let accuracy_value = (app.session.stats.accuracy.clamp(0.0, 100.0) as u16).min(100);
 
     if area.height < 5 {
-        let accuracy_label = format!("Accuracy: {:.1}%", app.stats.accuracy);
+        let accuracy_label = format!("Accuracy: {:.1}%", app.session.stats.accuracy);
         let accuracy_gauge = Gauge::default()
             .block(Block::default().borders(Borders::ALL).title("Accuracy"))
             .gauge_style(Style::default().fg(Color::Rgb(
@@ -835,7 +1082,7 @@ fn draw_gauges(app: &App, frame: &mut Frame, area: Rect) {
         .constraints([Constraint::Length(3), Constraint::Length(3)])
         .split(area);
 
-    let accuracy_label = format!("Accuracy: {:.1}%", app.stats.accuracy);
+    let accuracy_label = format!("Accuracy: {:.1}%", app.session.stats.accuracy);
     let accuracy_gauge = Gauge::default()
         .block(Block::default().borders(Borders::ALL).title("Accuracy"))
         .gauge_style(Style::default().fg(Color::Rgb(
@@ -847,19 +1094,20 @@ fn draw_gauges(app: &App, frame: &mut Frame, area: Rect) {
         .label(accuracy_label);
     frame.render_widget(accuracy_gauge, chunks[0]);
 
-    let progress = if app.text_source.full_text().is_empty() {
+    let progress = if app.session.text_source.is_empty() {
         0
-    } else if app.text_source.is_scrollable {
-        let total_words = app.text_source.total_words() as usize;
-        if total_words == 0 {
-            0
-        } else {
-            let typed_words = app.typed_text.split_whitespace().count();
-            ((typed_words * 100) / total_words) as u16
-        }
+    } else if app.session.text_source.is_scrollable {
+        let total_words = app.session.text_source.total_words() as usize;
+        let typed_words = app
+            .session
+            .typed_chars
+            .split(|c| c.is_whitespace())
+            .filter(|word| !word.is_empty())
+            .count();
+        (typed_words * 100).checked_div(total_words).unwrap_or(0) as u16
     } else {
-        ((app.typed_text.len().min(app.text_source.full_text().len()) * 100)
-            / app.text_source.full_text().len()) as u16
+        ((app.session.cursor_pos.min(app.session.text_source.len()) * 100)
+            / app.session.text_source.len()) as u16
     };
 
     let progress_value = progress.min(100);
@@ -879,8 +1127,8 @@ fn draw_gauges(app: &App, frame: &mut Frame, area: Rect) {
 
 fn draw_chart(app: &App, frame: &mut Frame, area: Rect) {
     if area.width < 20 || area.height < 4 {
-        if !app.stats.wpm_samples.is_empty() {
-            let latest_wpm = app.stats.wpm_samples.last().unwrap_or(&0.0);
+        if !app.session.stats.wpm_samples.is_empty() {
+            let latest_wpm = app.session.stats.wpm_samples.last().unwrap_or(&0.0);
             let placeholder = format!("WPM: {latest_wpm:.1}");
             let placeholder_widget = Paragraph::new(placeholder)
                 .block(Block::default().borders(Borders::ALL).title("Current WPM"))
@@ -890,16 +1138,16 @@ fn draw_chart(app: &App, frame: &mut Frame, area: Rect) {
         return;
     }
 
-    let effective_samples = if app.stats.wpm_samples.is_empty() {
+    let effective_samples = if app.session.stats.wpm_samples.is_empty() {
         vec![0.0]
     } else {
-        app.stats.wpm_samples.clone()
+        app.session.stats.wpm_samples.clone()
     };
 
-    let raw_wpm_samples = if app.stats.raw_wpm_samples.is_empty() {
+    let raw_wpm_samples = if app.session.stats.raw_wpm_samples.is_empty() {
         vec![0.0]
     } else {
-        app.stats.raw_wpm_samples.clone()
+        app.session.stats.raw_wpm_samples.clone()
     };
 
     let wpm_data: Vec<(f64, f64)> = effective_samples
@@ -1012,6 +1260,8 @@ fn draw_menu(app: &App, frame: &mut Frame, area: Rect) {
             MenuState::TimeMenu(_) => "Time Limit",
             MenuState::WordCountMenu(_) => "Word Count",
             MenuState::ThemeMenu(_) => "Theme",
+            MenuState::HistoryMenu(_) => "History",
+            MenuState::ProfileMenu(_) => "Profile",
             MenuState::Help => "Help",
             _ => "Menu",
         };
@@ -1037,7 +1287,12 @@ fn draw_menu(app: &App, frame: &mut Frame, area: Rect) {
         MenuState::ThemeMenu(_) => "THEME",
         MenuState::CustomTimedInput(_) => "CUSTOM TIMED TEST",
         MenuState::CustomWordsInput(_) => "CUSTOM WORDS TEST",
+        MenuState::CustomPaceInput(_) => "PACE CARET",
+        MenuState::LoadFileInput { .. } => "LOAD FILE",
+        MenuState::HistoryMenu(_) => "HISTORY",
         MenuState::SettingsMenu(_) => "SETTINGS",
+        MenuState::ProfileMenu(_) => "PROFILE",
+        MenuState::NewProfileInput { .. } => "NEW PROFILE",
         MenuState::Help => "HELP",
         MenuState::TestComplete => "TEST COMPLETE",
         _ => "",
@@ -1062,13 +1317,16 @@ fn draw_menu(app: &App, frame: &mut Frame, area: Rect) {
     let menu_text = match app.menu_state {
         MenuState::MainMenu(idx) => {
             let mut text = Vec::new();
+            let profile = format!("7. Profile: {}", app.profile);
             let items = vec![
                 ("1. Test Mode", idx == 0),
                 ("2. Difficulty", idx == 1),
                 ("3. Theme", idx == 2),
                 ("4. Settings", idx == 3),
-                ("5. Help", idx == 4),
-                ("6. Back", idx == 5),
+                ("5. History", idx == 4),
+                ("6. Help", idx == 5),
+                (profile.as_str(), idx == 6),
+                ("8. Back", idx == 7),
             ];
 
             for (item, selected) in items {
@@ -1078,12 +1336,68 @@ fn draw_menu(app: &App, frame: &mut Frame, area: Rect) {
                         Style::default().add_modifier(Modifier::REVERSED),
                     )]));
                 } else {
-                    text.push(Line::from(item));
+                    text.push(Line::from(item.to_string()));
                 }
             }
 
             text
         }
+        MenuState::ProfileMenu(idx) => {
+            let names = app.profiles.names();
+            let mut items: Vec<String> = names
+                .iter()
+                .map(|&name| {
+                    if name == app.profile {
+                        format!("{name} (current)")
+                    } else {
+                        name.to_string()
+                    }
+                })
+                .collect();
+            items.push("New profile...".to_string());
+            items.push("Back".to_string());
+
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    let item = format!("{}. {item}", i + 1);
+                    if i == idx {
+                        Line::from(vec![Span::styled(
+                            format!("> {item} <"),
+                            Style::default().add_modifier(Modifier::REVERSED),
+                        )])
+                    } else {
+                        Line::from(item)
+                    }
+                })
+                .collect()
+        }
+        MenuState::NewProfileInput {
+            ref name,
+            ref error,
+        } => {
+            let mut lines = vec![
+                Line::from(Span::styled(
+                    "ENTER PROFILE NAME:",
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Line::default(),
+                Line::from(vec![Span::styled(
+                    format!("{name}▋"),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )]),
+                Line::default(),
+            ];
+            if let Some(error) = error {
+                lines.push(Line::from(Span::styled(
+                    error.as_str(),
+                    Style::default().fg(Color::Red),
+                )));
+            }
+            lines.push(Line::from("Press ENTER to create"));
+            lines
+        }
         MenuState::TestModeMenu(idx) => {
             let items = [
                 ("1. Timed", idx == 0),
@@ -1201,52 +1515,66 @@ fn draw_menu(app: &App, frame: &mut Frame, area: Rect) {
         }
         MenuState::SettingsMenu(idx) => {
             let items = [
-                ("1. Toggle Repeat Mode", idx == 0),
-                ("2. Toggle End on First Error", idx == 1),
-                ("3. Back", idx == 2),
-            ];
-            vec![
-                if items[0].1 {
-                    Line::from(vec![Span::styled(
-                        format!("> {} <", items[0].0),
-                        Style::default().add_modifier(Modifier::REVERSED),
-                    )])
-                } else {
-                    Line::from(items[0].0)
-                },
-                Line::default(),
-                Line::from(format!(
-                    "Current: {}",
+                format!(
+                    "1. Repeat Mode: {}",
                     if app.config.repeat_test { "ON" } else { "OFF" }
-                )),
-                Line::default(),
-                if items[1].1 {
-                    Line::from(vec![Span::styled(
-                        format!("> {} <", items[1].0),
-                        Style::default().add_modifier(Modifier::REVERSED),
-                    )])
-                } else {
-                    Line::from(items[1].0)
-                },
-                Line::default(),
-                Line::from(format!(
-                    "Current: {}",
+                ),
+                format!(
+                    "2. End on First Error: {}",
                     if app.config.end_on_first_error {
                         "ON"
                     } else {
                         "OFF"
                     }
-                )),
-                Line::default(),
-                if items[2].1 {
-                    Line::from(vec![Span::styled(
-                        format!("> {} <", items[2].0),
-                        Style::default().add_modifier(Modifier::REVERSED),
-                    )])
-                } else {
-                    Line::from(items[2].0)
-                },
-            ]
+                ),
+                format!("3. Font Style: {}", font_style_name(app.config.font_style)),
+                format!(
+                    "4. Caret Style: {}",
+                    caret_style_name(app.config.caret_style)
+                ),
+                format!(
+                    "5. Terminal Cursor: {}",
+                    if app.config.use_terminal_cursor {
+                        "ON"
+                    } else {
+                        "OFF"
+                    }
+                ),
+                format!(
+                    "6. Pace Caret: {}{}",
+                    pace_caret_name(app.config.pace_caret, app.config.pace_caret_wpm),
+                    if app.missing_personal_best() {
+                        " (no PB yet)"
+                    } else {
+                        ""
+                    }
+                ),
+                format!("7. Pace Caret WPM: {}", app.config.pace_caret_wpm),
+                format!(
+                    "8. Key Debounce: {}",
+                    key_debounce_name(app.config.key_debounce, app.config.debounce_ms)
+                ),
+                format!(
+                    "9. Backspace: {}",
+                    backspace_mode_name(app.config.backspace_mode)
+                ),
+                "10. Back".to_string(),
+            ];
+
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    if i == idx {
+                        Line::from(vec![Span::styled(
+                            format!("> {item} <"),
+                            Style::default().add_modifier(Modifier::REVERSED),
+                        )])
+                    } else {
+                        Line::from(item)
+                    }
+                })
+                .collect()
         }
 
         MenuState::CustomTimedInput(ref input) => {
@@ -1294,17 +1622,149 @@ fn draw_menu(app: &App, frame: &mut Frame, area: Rect) {
                 Line::from("Press ENTER to confirm"),
             ]
         }
+        MenuState::CustomPaceInput(ref input) => {
+            vec![
+                Line::from(Span::styled(
+                    "ENTER PACE CARET WPM:",
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Line::default(),
+                Line::from(if input.is_empty() {
+                    vec![Span::styled(
+                        "▋",
+                        Style::default().add_modifier(Modifier::SLOW_BLINK),
+                    )]
+                } else {
+                    vec![Span::styled(
+                        format!("{input} ▋"),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    )]
+                }),
+                Line::default(),
+                Line::from("Press ENTER to confirm"),
+            ]
+        }
+        MenuState::LoadFileInput {
+            ref path,
+            ref error,
+        } => {
+            let mut lines = vec![
+                Line::from(Span::styled(
+                    "LOAD TEXT FROM FILE:",
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Line::default(),
+                Line::from(vec![Span::styled(
+                    format!("{path}▋"),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )]),
+                Line::default(),
+            ];
+            if let Some(error) = error {
+                lines.push(Line::from(Span::styled(
+                    error.as_str(),
+                    Style::default().fg(Color::Red),
+                )));
+            }
+            lines.push(Line::from("Press ENTER to load"));
+            lines
+        }
+        MenuState::HistoryMenu(idx) => {
+            let tests: Vec<_> = app
+                .history
+                .as_ref()
+                .map(|h| h.tests.iter().rev().collect())
+                .unwrap_or_default();
+
+            if tests.is_empty() {
+                vec![
+                    Line::from("No tests recorded yet"),
+                    Line::default(),
+                    Line::from("Complete a test to see it here"),
+                ]
+            } else {
+                let rows = (inner_area.height as usize).saturating_sub(6).max(1);
+                let first = idx.saturating_sub(rows - 1);
+
+                let mut lines = vec![
+                    Line::from(Span::styled(
+                        format!("{} TESTS - ENTER TO REPLAY", tests.len()),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    )),
+                    Line::default(),
+                ];
+
+                let mut hits = app.hit_regions.borrow_mut();
+                for (i, result) in tests.iter().enumerate().skip(first).take(rows) {
+                    let row = inner_area.y + (lines.len() as u16);
+                    if row < inner_area.bottom() {
+                        hits.push(
+                            Rect::new(inner_area.x, row, inner_area.width, 1),
+                            HitTarget::MenuItem(i),
+                        );
+                    }
+
+                    let item = format!(
+                        "{} | {} | {:.1} WPM | {:.1}%{}",
+                        app.local_time(result.timestamp).format("%Y-%m-%d %H:%M"),
+                        result.mode,
+                        result.wpm,
+                        result.accuracy,
+                        if Replay::can_replay(result) {
+                            ""
+                        } else {
+                            " | no recording"
+                        }
+                    );
+
+                    if i == idx {
+                        lines.push(Line::from(vec![Span::styled(
+                            format!("> {item} <"),
+                            Style::default().add_modifier(Modifier::REVERSED),
+                        )]));
+                    } else {
+                        lines.push(Line::from(item));
+                    }
+                }
+
+                lines
+            }
+        }
         MenuState::Help => {
+            let keys = &app.config.keybindings;
             vec![
                 Line::from(vec![Span::styled(
                     "KEYBOARD CONTROLS",
                     Style::default().add_modifier(Modifier::BOLD),
                 )]),
-                Line::from("• Esc: Open menu / Close menu / Cancel current test"),
-                Line::from("• Tab: Quick restart test"),
-                Line::from("• Ctrl+C: Exit application"),
+                Line::from(format!(
+                    "• {}: Open menu / Close menu / Cancel current test",
+                    keys.open_menu
+                )),
+                Line::from(format!("• {}: Quick restart test", keys.restart)),
+                Line::from(format!(
+                    "• {}: Next test from the results screen",
+                    keys.next_test
+                )),
+                Line::from(format!(
+                    "• {}: Repeat the same text from the results screen",
+                    keys.repeat_text
+                )),
+                Line::from(format!(
+                    "• {}: Show or hide the stats panel",
+                    keys.toggle_stats
+                )),
+                Line::from("• Ctrl+Backspace / Alt+Backspace / Ctrl+W: Delete the current word"),
+                Line::from(format!(
+                    "• {} or ':' before typing: Command palette for every setting and action",
+                    keys.command_palette
+                )),
+                Line::from(format!("• {}: Exit application", keys.quit)),
                 Line::from("• ↑/↓: Navigate menus or scroll help"),
                 Line::from("• Enter: Select menu option"),
+                Line::from("• j/k, g/G: Move down/up, jump to first/last; l selects, h goes back"),
+                Line::from("• Mouse: Click a menu item to select it, scroll help and history"),
+                Line::from("• Rebind keys in the keybindings section of the config file"),
                 Line::default(),
                 Line::from(vec![Span::styled(
                     "TEST MODES",
@@ -1314,6 +1774,14 @@ fn draw_menu(app: &App, frame: &mut Frame, area: Rect) {
                 Line::from("• Words: Type a specific number of words"),
                 Line::from("• Quote: Type a random quote"),
                 Line::from("• Custom: Type custom text (set in config file)"),
+                Line::from("• Seed: Every test has a seed shown on the results screen"),
+                Line::from(format!(
+                    "  - Press {} there to retry the exact same text",
+                    keys.repeat_text
+                )),
+                Line::from("  - Start with --seed N (or set seed in config) to share a text"),
+                Line::from("• Race: Start with --host PORT, others join with --join HOST:PORT"),
+                Line::from("  - The host presses ENTER to start; everyone types the same text"),
                 Line::default(),
                 Line::from(vec![Span::styled(
                     "SETTINGS",
@@ -1324,6 +1792,25 @@ fn draw_menu(app: &App, frame: &mut Frame, area: Rect) {
                 Line::from("  - Settings cannot be changed while active"),
                 Line::from("• End on First Error: Test stops on first mistake"),
                 Line::from("  - Useful for perfect accuracy practice"),
+                Line::from("• Font Style: Changes how the typing text is drawn"),
+                Line::from("  - Simple: Only mistakes are colored"),
+                Line::from("  - Monospace: Extra spacing between letters"),
+                Line::from("  - Large: Current word in big block letters"),
+                Line::from("• Caret Style: Block, Underline, Bar or Off"),
+                Line::from("• Terminal Cursor: Use the terminal's own cursor as the caret"),
+                Line::from("• Pace Caret: Race a second caret moving at a target pace"),
+                Line::from("  - Fixed WPM, your personal best run, or recent average"),
+                Line::from("• Backspace: Normal, Confidence or Off"),
+                Line::from("  - Confidence: Correctly typed words are locked once finished"),
+                Line::from("  - Deleted characters are counted as corrections"),
+                Line::default(),
+                Line::from(vec![Span::styled(
+                    "HISTORY & REPLAY",
+                    Style::default().add_modifier(Modifier::BOLD),
+                )]),
+                Line::from("• Recent tests and personal bests are saved with their keystrokes"),
+                Line::from("• Select a test in History to replay it"),
+                Line::from("• Space: Pause/resume   ←/→: Step   ↑/↓: Speed   R: Restart"),
                 Line::default(),
                 Line::from(vec![Span::styled(
                     "STATISTICS",
@@ -1356,11 +1843,12 @@ fn draw_menu(app: &App, frame: &mut Frame, area: Rect) {
             ]
         }
         MenuState::TestComplete => {
-            let duration = if let (Some(start), Some(end)) = (app.start_time, app.end_time) {
-                end.duration_since(start).as_secs_f64()
-            } else {
-                0.0
-            };
+            let duration =
+                if let (Some(start), Some(end)) = (app.session.start_time, app.session.end_time) {
+                    end.saturating_sub(start).as_secs_f64()
+                } else {
+                    0.0
+                };
 
             let mut lines = vec![
                 Line::from(Span::styled(
@@ -1368,13 +1856,13 @@ fn draw_menu(app: &App, frame: &mut Frame, area: Rect) {
                     Style::default().add_modifier(Modifier::BOLD),
                 )),
                 Line::default(),
-                Line::from(format!("WPM: {:.1}", app.stats.wpm)),
-                Line::from(format!("Raw WPM: {:.1}", app.stats.raw_wpm)),
-                Line::from(format!("Accuracy: {:.1}%", app.stats.accuracy)),
+                Line::from(format!("WPM: {:.1}", app.session.stats.wpm)),
+                Line::from(format!("Raw WPM: {:.1}", app.session.stats.raw_wpm)),
+                Line::from(format!("Accuracy: {:.1}%", app.session.stats.accuracy)),
                 Line::from(format!("Time: {duration:.1} seconds")),
             ];
 
-            if let Some(reason) = &app.test_end_reason {
+            if let Some(reason) = &app.session.test_end_reason {
                 lines.push(Line::default());
                 lines.push(Line::from(Span::styled(
                     reason,
@@ -1383,8 +1871,17 @@ fn draw_menu(app: &App, frame: &mut Frame, area: Rect) {
             }
 
             lines.push(Line::default());
-            lines.push(Line::from("Press ENTER to restart test"));
-            lines.push(Line::from("Press ESC to return to menu"));
+            let keys = &app.config.keybindings;
+            lines.push(Line::from(format!(
+                "Press {} to restart test",
+                key_hint(keys.next_test)
+            )));
+            if app.text_is_seeded() {
+                lines.push(Line::from(format!(
+                    "Press {} to retry this seed",
+                    key_hint(keys.repeat_text)
+                )));
+            }
 
             lines
         }
@@ -1403,6 +1900,26 @@ fn draw_menu(app: &App, frame: &mut Frame, area: Rect) {
         full_text.push(Line::from("Press ESC to return"));
     }
 
+    if app.menu_state == MenuState::Help {
+        let width = inner_area.width.max(1) as usize;
+        let rows: usize = full_text
+            .iter()
+            .map(|line| line.width().max(1).div_ceil(width))
+            .sum();
+        app.hit_regions.borrow_mut().help_scroll_max =
+            rows.saturating_sub(inner_area.height as usize);
+    } else if let Some(len) = app.menu_len()
+        && !matches!(app.menu_state, MenuState::HistoryMenu(_))
+    {
+        let mut hits = app.hit_regions.borrow_mut();
+        for i in 0..len.min(inner_area.height as usize) {
+            hits.push(
+                Rect::new(inner_area.x, inner_area.y + i as u16, inner_area.width, 1),
+                HitTarget::MenuItem(i),
+            );
+        }
+    }
+
     let menu_paragraph = if app.menu_state == MenuState::Help {
         Paragraph::new(full_text)
             .alignment(Alignment::Left)
@@ -1418,35 +1935,375 @@ fn draw_menu(app: &App, frame: &mut Frame, area: Rect) {
     frame.render_widget(menu_paragraph, inner_area);
 }
 
-fn draw_test_complete(app: &App, frame: &mut Frame, area: Rect) {
-    draw_test_complete_new(app, frame, area);
-}
+fn draw_command_palette(app: &App, frame: &mut Frame, area: Rect) {
+    if app.session.test_complete {
+        draw_test_complete(app, frame, area);
+    } else {
+        draw_typing_area(app, frame, area);
+    }
+    app.hit_regions.borrow_mut().clear();
 
-fn draw_warning(app: &App, frame: &mut Frame, area: Rect) {
-    let (action, _prev_state) = match &app.warning_state {
-        WarningState::RepeatModeSettings { action, prev_state } => (action, prev_state),
-        _ => return,
+    let MenuState::CommandPalette { query, selected } = &app.menu_state else {
+        return;
     };
 
-    let app_title = format!(
-        "TuiType{}",
-        if app.config.repeat_test {
-            " [Repeat Mode]"
+    let width = area.width.saturating_sub(8).min(60);
+    let height = area.height.saturating_sub(2).min(16);
+    if width < 20 || height < 5 {
+        return;
+    }
+
+    let popup = Rect::new(area.x + (area.width - width) / 2, area.y + 1, width, height);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(" Command Palette ")
+        .title_style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )
+        .border_style(Style::default().fg(Color::White));
+    let inner = block.inner(popup);
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(block, popup);
+
+    let results = app.palette_results();
+    let rows = (inner.height as usize).saturating_sub(2).max(1);
+    let first = selected.saturating_sub(rows - 1);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("> ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{query}▋")),
+        ]),
+        Line::default(),
+    ];
+
+    if results.is_empty() {
+        lines.push(Line::from("No matching commands"));
+    }
+
+    let mut hits = app.hit_regions.borrow_mut();
+    for (i, command) in results.iter().enumerate().skip(first).take(rows) {
+        hits.push(
+            Rect::new(inner.x, inner.y + lines.len() as u16, inner.width, 1),
+            HitTarget::MenuItem(i),
+        );
+
+        if i == *selected {
+            lines.push(Line::from(Span::styled(
+                format!("{:<width$}", command.label, width = inner.width as usize),
+                Style::default().add_modifier(Modifier::REVERSED),
+            )));
         } else {
-            ""
+            lines.push(Line::from(command.label));
         }
+    }
+
+    let paragraph = Paragraph::new(lines).style(Style::default().fg(Color::White));
+    frame.render_widget(paragraph, inner);
+}
+
+fn draw_replay(app: &App, frame: &mut Frame, area: Rect) {
+    let Some(replay) = app.replay.as_ref() else {
+        return;
+    };
+
+    let status = if replay.is_finished() {
+        "Finished"
+    } else if replay.paused {
+        "Paused"
+    } else {
+        "Playing"
+    };
+
+    let block = Block::default()
+        .title(format!(
+            "TuiType | Replay | {:.1}s / {:.1}s | Speed: {}x | {} | Keys: {}/{}",
+            replay.position_ms / 1000.0,
+            replay.duration_ms() as f64 / 1000.0,
+            replay.speed(),
+            status,
+            replay.applied_keystrokes(),
+            replay.total_keystrokes()
+        ))
+        .title_alignment(Alignment::Left)
+        .title_style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+
+    let inner_area = block.inner(area);
+    frame.render_widget(block, area);
+
+    if inner_area.height < 2 {
+        return;
+    }
+
+    let controls =
+        Paragraph::new("Space: Pause/Resume | ←/→: Step | ↑/↓: Speed | R: Restart | ESC: Back")
+            .style(Style::default().fg(Color::White));
+    frame.render_widget(
+        controls,
+        Rect::new(inner_area.x, inner_area.y, inner_area.width, 1),
+    );
+
+    let view = TextView {
+        target: &replay.text,
+        typed: &replay.typed,
+        cursor_pos: replay.cursor_pos(),
+        pace_pos: None,
+    };
+    let text_area = Rect::new(
+        inner_area.x,
+        inner_area.y + 2.min(inner_area.height - 1),
+        inner_area.width,
+        inner_area.height.saturating_sub(2),
+    );
+    render_text_view(app, &view, frame, text_area);
+}
+
+fn draw_race(app: &App, race: &Race, frame: &mut Frame, area: Rect) {
+    if race.phase == RacePhase::Lobby {
+        draw_race_lobby(race, frame, area);
+    } else if app.menu_state == MenuState::TestComplete || race.phase == RacePhase::Finished {
+        draw_race_standings(race, frame, area);
+    } else {
+        let panel_height = (race.players.len() as u16 + 2).min(area.height / 2);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(panel_height), Constraint::Min(5)])
+            .split(area);
+
+        draw_race_progress(race, frame, chunks[0]);
+        draw_typing_area(app, frame, chunks[1]);
+    }
+}
+
+fn race_block(title: &str) -> Block<'_> {
+    Block::default()
+        .title(title)
+        .title_alignment(Alignment::Left)
+        .title_style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+}
+
+fn race_player_name(race: &Race, player: &PlayerState) -> String {
+    if race.player_id == Some(player.id) {
+        format!("{} (you)", player.name)
+    } else {
+        player.name.clone()
+    }
+}
+
+fn draw_race_lobby(race: &Race, frame: &mut Frame, area: Rect) {
+    let block = race_block("TuiType | Race Lobby");
+    let inner_area = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut lines = vec![
+        Line::from(if race.is_host() {
+            format!("Hosting a race on {}", race.address)
+        } else {
+            format!("Joined race at {}", race.address)
+        }),
+        Line::default(),
+        Line::from(Span::styled(
+            format!("PLAYERS ({})", race.players.len()),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+    ];
+    lines.extend(
+        race.players
+            .iter()
+            .map(|player| Line::from(format!("• {}", race_player_name(race, player)))),
+    );
+    lines.push(Line::default());
+    lines.push(Line::from(if !race.is_connected() {
+        "Disconnected from the race host"
+    } else if race.is_host() {
+        "Press ENTER to start the race"
+    } else {
+        "Waiting for the host to start the race"
+    }));
+
+    frame.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center),
+        inner_area,
+    );
+}
+
+fn draw_race_progress(race: &Race, frame: &mut Frame, area: Rect) {
+    let block = race_block("TuiType | Race");
+    let inner_area = block.inner(area);
+    frame.render_widget(block, area);
+
+    let is_timed = matches!(
+        race.settings.as_ref().map(|settings| settings.test_mode),
+        Some(crate::config::TestMode::Timed(_))
+    );
+    let leader = race
+        .players
+        .iter()
+        .map(|player| player.position)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let name_width = race
+        .players
+        .iter()
+        .map(|player| race_player_name(race, player).chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(20);
+    let bar_width = (inner_area.width as usize).saturating_sub(name_width + 14);
+
+    let lines: Vec<Line> = race
+        .players
+        .iter()
+        .map(|player| {
+            let progress = if is_timed {
+                player.position as f64 / leader as f64
+            } else {
+                player.progress()
+            };
+            let filled = ((bar_width as f64) * progress.clamp(0.0, 1.0)).round() as usize;
+
+            let name: String = race_player_name(race, player)
+                .chars()
+                .take(name_width)
+                .collect();
+            let style = if race.player_id == Some(player.id) {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+
+            Line::from(vec![
+                Span::styled(format!("{name:<name_width$} "), style),
+                Span::styled("█".repeat(filled), Style::default().fg(Color::Green)),
+                Span::styled(
+                    "░".repeat(bar_width - filled),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(format!(" {:>6.1} WPM", player.wpm), style),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), inner_area);
+}
+
+fn draw_race_standings(race: &Race, frame: &mut Frame, area: Rect) {
+    let block = race_block(if race.phase == RacePhase::Finished {
+        "TuiType | Race Results"
+    } else {
+        "TuiType | Race Standings"
+    });
+    let inner_area = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "{:<5}{:<22}{:>10}{:>11}{:>9}",
+                "#", "PLAYER", "WPM", "ACCURACY", "TIME"
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::default(),
+    ];
+
+    for (place, player) in race.standings().iter().enumerate() {
+        let name: String = race_player_name(race, player).chars().take(20).collect();
+        let row = match &player.result {
+            Some(result) => format!(
+                "{:<5}{:<22}{:>10.1}{:>10.1}%{:>8.1}s",
+                place + 1,
+                name,
+                result.wpm,
+                result.accuracy,
+                result.elapsed_ms as f64 / 1000.0
+            ),
+            None => format!(
+                "{:<5}{:<22}{:>10.1}{:>20}",
+                "-",
+                name,
+                player.wpm,
+                format!("typing {:.0}%", player.progress() * 100.0)
+            ),
+        };
+
+        let style = if race.player_id == Some(player.id) {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(Span::styled(row, style)));
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::from(
+        match (race.phase, race.is_host(), race.is_connected()) {
+            (_, _, false) => "Disconnected from the race host",
+            (RacePhase::Racing, _, _) => "Waiting for the other players to finish",
+            (_, true, _) => "Press ENTER to start a new race",
+            (_, false, _) => "Waiting for the host to start the next race",
+        },
+    ));
+
+    frame.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center),
+        inner_area,
     );
+}
+
+fn draw_test_complete(app: &App, frame: &mut Frame, area: Rect) {
+    draw_test_complete_new(app, frame, area);
+}
+
+fn key_hint(chord: KeyChord) -> String {
+    chord.to_string().to_uppercase()
+}
+
+fn draw_warning(app: &App, frame: &mut Frame, area: Rect) {
+    match &app.warning_state {
+        WarningState::RepeatModeSettings { action, .. } => {
+            draw_repeat_mode_warning(app, frame, area, action)
+        }
+        WarningState::ConfigRecovered { messages } => draw_config_warning(frame, area, messages),
+        WarningState::None => {}
+    }
+}
 
+fn warning_popup(
+    frame: &mut Frame,
+    area: Rect,
+    title: String,
+    height: u16,
+    fallback: &str,
+) -> Option<Rect> {
     let width = area.width.saturating_sub(10).clamp(30, 80).min(area.width);
-    let height = 10.min(area.height.saturating_sub(4)).min(area.height);
+    let height = height.min(area.height.saturating_sub(4)).min(area.height);
 
     if width < 30 || height < 5 {
-        let text = "Warning: Repeat Mode active\nPress ENTER to disable";
-        let paragraph = Paragraph::new(text)
+        let paragraph = Paragraph::new(fallback)
             .alignment(Alignment::Center)
             .style(Style::default().fg(Color::Red));
         frame.render_widget(paragraph, area);
-        return;
+        return None;
     }
 
     let x = area.x + (area.width.saturating_sub(width)) / 2;
@@ -1460,7 +2317,7 @@ fn draw_warning(app: &App, frame: &mut Frame, area: Rect) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Red))
-        .title(format!(" {app_title} - REPEAT MODE WARNING "))
+        .title(title)
         .title_style(
             Style::default()
                 .fg(Color::White)
@@ -1469,7 +2326,71 @@ fn draw_warning(app: &App, frame: &mut Frame, area: Rect) {
 
     frame.render_widget(block.clone(), popup_area);
 
-    let inner_area = block.inner(popup_area);
+    Some(block.inner(popup_area))
+}
+
+fn draw_config_warning(frame: &mut Frame, area: Rect, messages: &[String]) {
+    let height = 7 + 2 * messages.len() as u16;
+    let Some(inner_area) = warning_popup(
+        frame,
+        area,
+        " TuiType - CONFIG WARNING ".to_string(),
+        height,
+        "Warning: settings were recovered\nPress ENTER to continue",
+    ) else {
+        return;
+    };
+
+    let mut message_lines = vec![
+        Line::from(vec![Span::styled(
+            "SETTINGS RECOVERED",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::default(),
+    ];
+    for message in messages {
+        message_lines.push(Line::from(message.as_str()));
+    }
+    message_lines.push(Line::default());
+    message_lines.push(Line::from(vec![
+        Span::styled(
+            "ENTER",
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(": Continue"),
+    ]));
+
+    let warning_paragraph = Paragraph::new(message_lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().fg(Color::White));
+
+    frame.render_widget(warning_paragraph, inner_area);
+}
+
+fn draw_repeat_mode_warning(app: &App, frame: &mut Frame, area: Rect, action: &str) {
+    let app_title = format!(
+        "TuiType{}",
+        if app.config.repeat_test {
+            " [Repeat Mode]"
+        } else {
+            ""
+        }
+    );
+
+    let Some(inner_area) = warning_popup(
+        frame,
+        area,
+        format!(" {app_title} - REPEAT MODE WARNING "),
+        10,
+        "Warning: Repeat Mode active\nPress ENTER to disable",
+    ) else {
+        return;
+    };
 
     let message_lines = if inner_area.height >= 8 {
         vec![
@@ -1478,7 +2399,7 @@ fn draw_warning(app: &App, frame: &mut Frame, area: Rect) {
                 Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
             )]),
             Line::default(),
-            Line::from(action.as_str()),
+            Line::from(action),
             Line::default(),
             Line::from("Changing settings during Repeat Mode would affect test consistency."),
             Line::default(),
@@ -1507,7 +2428,7 @@ fn draw_warning(app: &App, frame: &mut Frame, area: Rect) {
                 "SETTINGS RESTRICTED",
                 Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
             )]),
-            Line::from(action.as_str()),
+            Line::from(action),
             Line::from("ENTER: Disable Repeat Mode"),
             Line::from("ESC: Cancel"),
         ]
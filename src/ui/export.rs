@@ -0,0 +1,125 @@
+use crate::AppResult;
+use crate::ui::{App, render};
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportedCell {
+    pub symbol: String,
+
+    pub fg: Option<String>,
+
+    pub bg: Option<String>,
+
+    pub modifiers: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BufferExport {
+    pub width: u16,
+
+    pub height: u16,
+
+    pub cells: Vec<ExportedCell>,
+}
+
+impl From<&Buffer> for BufferExport {
+    fn from(buffer: &Buffer) -> Self {
+        let cells = buffer
+            .content
+            .iter()
+            .map(|cell| ExportedCell {
+                symbol: cell.symbol().to_string(),
+                fg: css_color(cell.fg),
+                bg: css_color(cell.bg),
+                modifiers: cell
+                    .modifier
+                    .iter_names()
+                    .map(|(name, _)| name.to_lowercase())
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            width: buffer.area.width,
+            height: buffer.area.height,
+            cells,
+        }
+    }
+}
+
+pub fn render_to_buffer(app: &App, width: u16, height: u16) -> AppResult<Buffer> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    let mut rendered = Ok(());
+    terminal.draw(|frame| rendered = render(app, frame))?;
+    rendered?;
+
+    Ok(terminal.backend().buffer().clone())
+}
+
+pub fn css_color(color: Color) -> Option<String> {
+    let (r, g, b) = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(index) => indexed_rgb(index),
+        Color::Black => indexed_rgb(0),
+        Color::Red => indexed_rgb(1),
+        Color::Green => indexed_rgb(2),
+        Color::Yellow => indexed_rgb(3),
+        Color::Blue => indexed_rgb(4),
+        Color::Magenta => indexed_rgb(5),
+        Color::Cyan => indexed_rgb(6),
+        Color::Gray => indexed_rgb(7),
+        Color::DarkGray => indexed_rgb(8),
+        Color::LightRed => indexed_rgb(9),
+        Color::LightGreen => indexed_rgb(10),
+        Color::LightYellow => indexed_rgb(11),
+        Color::LightBlue => indexed_rgb(12),
+        Color::LightMagenta => indexed_rgb(13),
+        Color::LightCyan => indexed_rgb(14),
+        Color::White => indexed_rgb(15),
+    };
+
+    Some(format!("#{r:02x}{g:02x}{b:02x}"))
+}
+
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    const ANSI: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    match index {
+        0..=15 => ANSI[index as usize],
+        16..=231 => {
+            let cube = index - 16;
+            (
+                LEVELS[(cube / 36) as usize],
+                LEVELS[(cube / 6 % 6) as usize],
+                LEVELS[(cube % 6) as usize],
+            )
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
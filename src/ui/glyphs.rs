@@ -0,0 +1,55 @@
+pub const GLYPH_HEIGHT: usize = 5;
+
+pub const GLYPH_WIDTH: usize = 3;
+
+pub fn glyph(ch: char) -> [&'static str; GLYPH_HEIGHT] {
+    match ch.to_ascii_lowercase() {
+        'a' => [".#.", "#.#", "###", "#.#", "#.#"],
+        'b' => ["##.", "#.#", "##.", "#.#", "##."],
+        'c' => [".##", "#..", "#..", "#..", ".##"],
+        'd' => ["##.", "#.#", "#.#", "#.#", "##."],
+        'e' => ["###", "#..", "##.", "#..", "###"],
+        'f' => ["###", "#..", "##.", "#..", "#.."],
+        'g' => [".##", "#..", "#.#", "#.#", ".##"],
+        'h' => ["#.#", "#.#", "###", "#.#", "#.#"],
+        'i' => ["###", ".#.", ".#.", ".#.", "###"],
+        'j' => ["..#", "..#", "..#", "#.#", ".#."],
+        'k' => ["#.#", "#.#", "##.", "#.#", "#.#"],
+        'l' => ["#..", "#..", "#..", "#..", "###"],
+        'm' => ["#.#", "###", "###", "#.#", "#.#"],
+        'n' => ["##.", "#.#", "#.#", "#.#", "#.#"],
+        'o' => [".#.", "#.#", "#.#", "#.#", ".#."],
+        'p' => ["##.", "#.#", "##.", "#..", "#.."],
+        'q' => [".#.", "#.#", "#.#", "##.", ".##"],
+        'r' => ["##.", "#.#", "##.", "#.#", "#.#"],
+        's' => [".##", "#..", ".#.", "..#", "##."],
+        't' => ["###", ".#.", ".#.", ".#.", ".#."],
+        'u' => ["#.#", "#.#", "#.#", "#.#", "###"],
+        'v' => ["#.#", "#.#", "#.#", "#.#", ".#."],
+        'w' => ["#.#", "#.#", "###", "###", "#.#"],
+        'x' => ["#.#", "#.#", ".#.", "#.#", "#.#"],
+        'y' => ["#.#", "#.#", ".#.", ".#.", ".#."],
+        'z' => ["###", "..#", ".#.", "#..", "###"],
+        '0' => ["###", "#.#", "#.#", "#.#", "###"],
+        '1' => [".#.", "##.", ".#.", ".#.", "###"],
+        '2' => ["##.", "..#", ".#.", "#..", "###"],
+        '3' => ["##.", "..#", ".#.", "..#", "##."],
+        '4' => ["#.#", "#.#", "###", "..#", "..#"],
+        '5' => ["###", "#..", "##.", "..#", "##."],
+        '6' => [".##", "#..", "###", "#.#", "###"],
+        '7' => ["###", "..#", ".#.", ".#.", ".#."],
+        '8' => ["###", "#.#", "###", "#.#", "###"],
+        '9' => ["###", "#.#", "###", "..#", "##."],
+        '.' => ["...", "...", "...", "...", ".#."],
+        ',' => ["...", "...", "...", ".#.", "#.."],
+        '\'' => [".#.", ".#.", "...", "...", "..."],
+        '"' => ["#.#", "#.#", "...", "...", "..."],
+        '!' => [".#.", ".#.", ".#.", "...", ".#."],
+        '?' => ["##.", "..#", ".#.", "...", ".#."],
+        '-' => ["...", "...", "###", "...", "..."],
+        ':' => ["...", ".#.", "...", ".#.", "..."],
+        ';' => ["...", ".#.", "...", ".#.", "#.."],
+        ' ' => ["...", "...", "...", "...", "..."],
+        _ => ["###", "#.#", "#.#", "#.#", "###"],
+    }
+}
@@ -0,0 +1,41 @@
+use ratatui::layout::Rect;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitTarget {
+    MenuItem(usize),
+
+    Restart,
+
+    RetrySeed,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HitRegions {
+    regions: Vec<(Rect, HitTarget)>,
+
+    pub help_scroll_max: usize,
+}
+
+impl HitRegions {
+    pub fn clear(&mut self) {
+        self.regions.clear();
+        self.help_scroll_max = 0;
+    }
+
+    pub fn push(&mut self, area: Rect, target: HitTarget) {
+        if area.width > 0 && area.height > 0 {
+            self.regions.push((area, target));
+        }
+    }
+
+    pub fn target_at(&self, column: u16, row: u16) -> Option<HitTarget> {
+        self.regions
+            .iter()
+            .rev()
+            .find(|(area, _)| {
+                (area.x..area.x + area.width).contains(&column)
+                    && (area.y..area.y + area.height).contains(&row)
+            })
+            .map(|&(_, target)| target)
+    }
+}
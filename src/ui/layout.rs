@@ -0,0 +1,72 @@
+use std::ops::Range;
+use unicode_width::UnicodeWidthChar;
+
+pub const VISIBLE_LINES: usize = 3;
+
+pub fn cell_width(ch: char, letter_spacing: bool) -> usize {
+    let width = ch.width().unwrap_or(0).max(1);
+    if letter_spacing && ch != ' ' {
+        width + 1
+    } else {
+        width
+    }
+}
+
+pub fn wrap_words(chars: &[char], width: usize, letter_spacing: bool) -> Vec<Range<usize>> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    let mut line_width = 0;
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let word_end = chars[pos..]
+            .iter()
+            .position(|&c| c == ' ')
+            .map(|i| pos + i)
+            .unwrap_or(chars.len());
+        let token_end = chars[word_end..]
+            .iter()
+            .position(|&c| c != ' ')
+            .map(|i| word_end + i)
+            .unwrap_or(chars.len());
+
+        let word_width: usize = chars[pos..word_end]
+            .iter()
+            .map(|&c| cell_width(c, letter_spacing))
+            .sum();
+
+        if line_width > 0 && line_width + word_width > width {
+            lines.push(line_start..pos);
+            line_start = pos;
+            line_width = 0;
+        }
+
+        for (i, &c) in chars[pos..token_end].iter().enumerate() {
+            let w = cell_width(c, letter_spacing);
+            let is_word_char = pos + i < word_end;
+            if is_word_char && line_width > 0 && line_width + w > width {
+                lines.push(line_start..pos + i);
+                line_start = pos + i;
+                line_width = 0;
+            }
+            line_width += w;
+        }
+
+        pos = token_end;
+    }
+
+    if line_start < chars.len() || lines.is_empty() {
+        lines.push(line_start..chars.len());
+    }
+
+    lines
+}
+
+pub fn visible_line_range(line_count: usize, active_line: usize, rows: usize) -> Range<usize> {
+    let rows = rows.min(line_count).max(1);
+    let first = active_line
+        .saturating_sub(rows / 2)
+        .min(line_count.saturating_sub(rows));
+    first..(first + rows).min(line_count)
+}
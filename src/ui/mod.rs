@@ -1,19 +1,40 @@
 mod draw;
+mod export;
+mod glyphs;
+mod hit;
+mod layout;
+mod palette;
 mod themes;
 
 use crate::AppResult;
-use crate::config::{Config, Difficulty, TestMode, ThemeConfig};
+use crate::clock::{SharedClock, system_clock};
+use crate::config::{
+    CaretStyle, Config, Difficulty, LocalConfig, LocalOverrides, PaceCaret, TestMode, ThemeConfig,
+    test_mode_name,
+};
 use crate::input::Event;
-use crate::stats::TypingStats;
-use crate::text::TextSource;
-use std::collections::HashMap;
-use std::time::Instant;
+use crate::input::keys::{MouseButton, MouseEvent, MouseEventKind};
+use crate::net::{Race, RacePhase, RaceSettings};
+use crate::pace::Ghost;
+use crate::profile::{DEFAULT_PROFILE, Profiles, profile_storage, validate_name};
+use crate::replay::Replay;
+use crate::session::TypingSession;
+use crate::stats::{TestHistory, TestResult};
+use crate::storage::{SharedStorage, default_storage};
+use crate::text::{TextSource, random_seed};
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use std::cell::RefCell;
+use std::time::Duration;
 
 pub use draw::render;
+pub use export::{BufferExport, ExportedCell, css_color, render_to_buffer};
+pub use hit::{HitRegions, HitTarget};
+pub use palette::{PaletteAction, PaletteCommand};
 pub use themes::{ThemeType, get_theme};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum MenuState {
+    #[default]
     Typing,
 
     MainMenu(usize),
@@ -37,10 +58,69 @@ pub enum MenuState {
     CustomTimedInput(String),
 
     CustomWordsInput(String),
+
+    CustomPaceInput(String),
+
+    HistoryMenu(usize),
+
+    Replay(usize),
+
+    CommandPalette {
+        query: String,
+
+        selected: usize,
+    },
+
+    LoadFileInput {
+        path: String,
+
+        error: Option<String>,
+    },
+
+    ProfileMenu(usize),
+
+    NewProfileInput {
+        name: String,
+
+        error: Option<String>,
+    },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl MenuState {
+    pub fn selected(&self) -> Option<usize> {
+        match *self {
+            MenuState::MainMenu(idx)
+            | MenuState::TestModeMenu(idx)
+            | MenuState::DifficultyMenu(idx)
+            | MenuState::TimeMenu(idx)
+            | MenuState::WordCountMenu(idx)
+            | MenuState::ThemeMenu(idx)
+            | MenuState::SettingsMenu(idx)
+            | MenuState::HistoryMenu(idx)
+            | MenuState::ProfileMenu(idx) => Some(idx),
+            _ => None,
+        }
+    }
+
+    pub fn with_selected(&self, idx: usize) -> MenuState {
+        match self {
+            MenuState::MainMenu(_) => MenuState::MainMenu(idx),
+            MenuState::TestModeMenu(_) => MenuState::TestModeMenu(idx),
+            MenuState::DifficultyMenu(_) => MenuState::DifficultyMenu(idx),
+            MenuState::TimeMenu(_) => MenuState::TimeMenu(idx),
+            MenuState::WordCountMenu(_) => MenuState::WordCountMenu(idx),
+            MenuState::ThemeMenu(_) => MenuState::ThemeMenu(idx),
+            MenuState::SettingsMenu(_) => MenuState::SettingsMenu(idx),
+            MenuState::HistoryMenu(_) => MenuState::HistoryMenu(idx),
+            MenuState::ProfileMenu(_) => MenuState::ProfileMenu(idx),
+            other => other.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum WarningState {
+    #[default]
     None,
 
     RepeatModeSettings {
@@ -48,18 +128,10 @@ pub enum WarningState {
 
         prev_state: Box<MenuState>,
     },
-}
-
-impl Default for WarningState {
-    fn default() -> Self {
-        Self::None
-    }
-}
 
-impl Default for MenuState {
-    fn default() -> Self {
-        Self::Typing
-    }
+    ConfigRecovered {
+        messages: Vec<String>,
+    },
 }
 
 pub struct App {
@@ -67,69 +139,227 @@ pub struct App {
 
     pub running: bool,
 
-    pub text_source: TextSource,
-
-    pub typed_text: String,
+    pub session: TypingSession,
 
-    pub cursor_pos: usize,
+    pub theme: ThemeConfig,
 
-    pub stats: TypingStats,
+    pub menu_state: MenuState,
 
-    pub start_time: Option<Instant>,
+    pub warning_state: WarningState,
 
-    pub end_time: Option<Instant>,
+    pub help_scroll_offset: usize,
 
-    pub test_complete: bool,
+    last_restart: Duration,
+    clock: SharedClock,
+    storage: SharedStorage,
+    utc_offset: Option<FixedOffset>,
+    profile: String,
+    profiles: Profiles,
+    local_overrides: Option<LocalOverrides>,
+    result_recorded: bool,
+    seed: Option<u64>,
 
-    pub theme: ThemeConfig,
+    pub history: Option<TestHistory>,
 
-    pub menu_state: MenuState,
+    pub ghost: Option<Ghost>,
 
-    pub time_remaining: Option<u32>,
+    pub pace_pos: Option<usize>,
 
-    pub warning_state: WarningState,
+    pub replay: Option<Replay>,
 
-    pub help_scroll_offset: usize,
+    pub race: Option<Race>,
 
-    key_timestamps: HashMap<crossterm::event::KeyCode, Instant>,
-    last_restart: Instant,
+    pub show_stats: bool,
 
-    pub test_end_reason: Option<String>,
+    notice: Option<String>,
+    needs_redraw: bool,
+    hit_regions: RefCell<HitRegions>,
 }
 
 impl App {
     pub fn new(config: Config) -> Self {
-        let text_source = TextSource::new(&config);
-        let theme = get_theme(config.theme_type);
+        Self::with_clock(config, system_clock())
+    }
 
-        let time_remaining = match config.test_mode {
-            TestMode::Timed(seconds) => Some(seconds),
-            _ => None,
-        };
+    pub fn with_clock(config: Config, clock: SharedClock) -> Self {
+        let session = TypingSession::with_clock(&config, clock.clone());
+        let theme = get_theme(config.theme_type);
+        let seed = config.seed;
 
         Self {
             config,
             running: true,
-            text_source,
-            typed_text: String::new(),
-            cursor_pos: 0,
-            stats: TypingStats::default(),
-            start_time: None,
-            end_time: None,
-            test_complete: false,
+            session,
             theme,
             menu_state: MenuState::default(),
-            time_remaining,
             warning_state: WarningState::default(),
             help_scroll_offset: 0,
-            key_timestamps: HashMap::new(),
-            last_restart: Instant::now(),
-            test_end_reason: None,
+            last_restart: clock.now(),
+            clock,
+            storage: default_storage(),
+            utc_offset: None,
+            profile: DEFAULT_PROFILE.to_string(),
+            profiles: Profiles::default(),
+            local_overrides: None,
+            result_recorded: false,
+            seed,
+            history: None,
+            ghost: None,
+            pace_pos: None,
+            replay: None,
+            race: None,
+            show_stats: true,
+            notice: None,
+            needs_redraw: true,
+            hit_regions: RefCell::default(),
+        }
+    }
+
+    pub fn set_history(&mut self, history: TestHistory) {
+        self.history = Some(history);
+    }
+
+    pub fn set_storage(&mut self, storage: SharedStorage) {
+        self.storage = storage;
+    }
+
+    pub fn set_utc_offset(&mut self, offset: FixedOffset) {
+        self.utc_offset = Some(offset);
+    }
+
+    pub fn local_time(&self, timestamp: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self.utc_offset {
+            Some(offset) => timestamp.with_timezone(&offset),
+            None => timestamp.with_timezone(&Local).fixed_offset(),
+        }
+    }
+
+    pub fn save_config(&self) -> AppResult<()> {
+        let storage = self.profile_storage();
+        match &self.local_overrides {
+            Some(overrides) => overrides.user_config(&self.config)?.save_to(&*storage),
+            None => self.config.save_to(&*storage),
         }
     }
 
-    fn handle_menu_keys(&mut self, key_event: crossterm::event::KeyEvent) -> AppResult<()> {
-        use crossterm::event::KeyCode;
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    pub fn missing_personal_best(&self) -> bool {
+        self.config.pace_caret == PaceCaret::PersonalBest
+            && self.history.as_ref().is_none_or(|history| {
+                history
+                    .personal_best(&test_mode_name(self.config.test_mode))
+                    .is_none()
+            })
+    }
+
+    pub fn text_is_seeded(&self) -> bool {
+        let repeated = self.config.repeat_test && self.config.last_test_text.is_some();
+        !repeated && !matches!(self.config.test_mode, TestMode::Custom)
+    }
+
+    fn profile_storage(&self) -> SharedStorage {
+        profile_storage(&self.storage, &self.profile)
+    }
+
+    pub fn load_profile(&mut self, name: Option<&str>) -> AppResult<Vec<String>> {
+        let mut profiles = Profiles::load_from(&*self.storage).unwrap_or_default();
+        let name = match name {
+            Some(name) => validate_name(name)?,
+            None => profiles.last_used().to_string(),
+        };
+        let changed = profiles.select(&name);
+
+        let storage = profile_storage(&self.storage, &name);
+        let loaded = Config::load_from(&*storage);
+        let mut warnings = loaded.warnings;
+        if changed && let Err(err) = profiles.save_to(&*self.storage) {
+            warnings.push(format!(
+                "The profile list could not be saved ({err:#}). Start with --profile {name} to use this profile again."
+            ));
+        }
+        self.history = match TestHistory::load_from(&*storage) {
+            Ok(history) => Some(history),
+            Err(err) => {
+                warnings.push(format!(
+                    "History could not be loaded ({err:#}). New results won't be saved."
+                ));
+                None
+            }
+        };
+
+        self.profile = name;
+        self.profiles = profiles;
+        self.config = loaded.config;
+        match self.local_overrides.take() {
+            Some(overrides) => warnings.extend(self.apply_local_config(overrides.into_local())),
+            None => self.config_changed(),
+        }
+        Ok(warnings)
+    }
+
+    fn switch_profile(&mut self, name: &str) {
+        match self.load_profile(Some(name)) {
+            Ok(warnings) => {
+                self.menu_state = MenuState::Typing;
+                self.show_config_warnings(warnings);
+            }
+            Err(err) => {
+                self.menu_state = MenuState::NewProfileInput {
+                    name: name.to_string(),
+                    error: Some(format!("{err:#}")),
+                };
+            }
+        }
+    }
+
+    fn config_changed(&mut self) {
+        self.theme = get_theme(self.config.theme_type);
+        self.seed = self.config.seed;
+        self.restart_test();
+    }
+
+    pub fn apply_local_config(&mut self, local: LocalConfig) -> Vec<String> {
+        let (overrides, rejected) = local.apply(&self.config);
+        let warnings = if rejected.is_empty() {
+            Vec::new()
+        } else {
+            vec![format!(
+                "Ignored invalid settings in {}: {}.",
+                overrides.path().display(),
+                rejected.join(", ")
+            )]
+        };
+
+        self.config = overrides.config().clone();
+        self.local_overrides = Some(overrides);
+        self.config_changed();
+        warnings
+    }
+
+    pub fn show_config_warnings(&mut self, messages: Vec<String>) {
+        if !messages.is_empty() {
+            self.warning_state = WarningState::ConfigRecovered { messages };
+        }
+    }
+
+    pub fn show_notice(&mut self, message: impl Into<String>) {
+        self.notice = Some(message.into());
+        self.needs_redraw = true;
+    }
+
+    pub fn notice(&self) -> Option<&str> {
+        self.notice.as_deref()
+    }
+
+    pub fn set_race(&mut self, race: Race) {
+        self.race = Some(race);
+    }
+
+    fn handle_menu_keys(&mut self, key_event: crate::input::keys::KeyEvent) -> AppResult<()> {
+        use crate::input::keys::KeyCode;
 
         match (self.menu_state.clone(), key_event.code) {
             (MenuState::MainMenu(idx), KeyCode::Left) | (MenuState::MainMenu(idx), KeyCode::Up) => {
@@ -137,7 +367,7 @@ impl App {
             }
             (MenuState::MainMenu(idx), KeyCode::Right)
             | (MenuState::MainMenu(idx), KeyCode::Down) => {
-                let menu_items_count = 6;
+                let menu_items_count = 8;
                 self.menu_state = MenuState::MainMenu((idx + 1).min(menu_items_count - 1));
             }
             (MenuState::MainMenu(idx), KeyCode::Enter) => match idx {
@@ -145,10 +375,63 @@ impl App {
                 1 => self.menu_state = MenuState::DifficultyMenu(0),
                 2 => self.menu_state = MenuState::ThemeMenu(0),
                 3 => self.menu_state = MenuState::SettingsMenu(0),
-                4 => self.menu_state = MenuState::Help,
+                4 => self.menu_state = MenuState::HistoryMenu(0),
+                5 => self.menu_state = MenuState::Help,
+                6 => {
+                    let current = self
+                        .profiles
+                        .names()
+                        .iter()
+                        .position(|&name| name == self.profile);
+                    self.menu_state = MenuState::ProfileMenu(current.unwrap_or(0));
+                }
                 _ => self.menu_state = MenuState::Typing,
             },
 
+            (MenuState::ProfileMenu(idx), KeyCode::Up) => {
+                self.menu_state = MenuState::ProfileMenu(idx.saturating_sub(1));
+            }
+            (MenuState::ProfileMenu(idx), KeyCode::Down) => {
+                let count = self.profiles.names().len() + 2;
+                self.menu_state = MenuState::ProfileMenu((idx + 1).min(count - 1));
+            }
+            (MenuState::ProfileMenu(idx), KeyCode::Enter) => {
+                let names = self.profiles.names();
+                match names.get(idx) {
+                    Some(&name) => {
+                        let name = name.to_string();
+                        self.switch_profile(&name);
+                    }
+                    None if idx == names.len() => {
+                        self.menu_state = MenuState::NewProfileInput {
+                            name: String::new(),
+                            error: None,
+                        };
+                    }
+                    None => self.menu_state = MenuState::MainMenu(6),
+                }
+            }
+            (MenuState::ProfileMenu(_), KeyCode::Esc) => {
+                self.menu_state = MenuState::MainMenu(6);
+            }
+
+            (MenuState::NewProfileInput { .. }, KeyCode::Esc) => {
+                self.menu_state = MenuState::ProfileMenu(self.profiles.names().len());
+            }
+            (MenuState::NewProfileInput { name, .. }, KeyCode::Char(c)) => {
+                self.menu_state = MenuState::NewProfileInput {
+                    name: format!("{name}{c}"),
+                    error: None,
+                };
+            }
+            (MenuState::NewProfileInput { mut name, .. }, KeyCode::Backspace) => {
+                name.pop();
+                self.menu_state = MenuState::NewProfileInput { name, error: None };
+            }
+            (MenuState::NewProfileInput { name, .. }, KeyCode::Enter) => {
+                self.switch_profile(&name);
+            }
+
             (MenuState::TestModeMenu(idx), KeyCode::Left)
             | (MenuState::TestModeMenu(idx), KeyCode::Up) => {
                 self.menu_state = MenuState::TestModeMenu(idx.saturating_sub(1));
@@ -217,7 +500,6 @@ impl App {
                     }
 
                     self.config.test_mode = TestMode::Timed(seconds);
-                    self.time_remaining = Some(seconds);
                     self.menu_state = MenuState::Typing;
                     self.restart_test();
                 }
@@ -334,7 +616,7 @@ impl App {
                 };
                 self.set_theme(theme);
                 self.config.theme_type = theme;
-                self.config.save().ok();
+                self.save_config().ok();
                 self.menu_state = MenuState::Typing;
             }
 
@@ -344,7 +626,7 @@ impl App {
             }
             (MenuState::SettingsMenu(idx), KeyCode::Right)
             | (MenuState::SettingsMenu(idx), KeyCode::Down) => {
-                self.menu_state = MenuState::SettingsMenu((idx + 1).min(2));
+                self.menu_state = MenuState::SettingsMenu((idx + 1).min(9));
             }
             (MenuState::SettingsMenu(idx), KeyCode::Enter) => match idx {
                 0 => {
@@ -352,20 +634,89 @@ impl App {
                     if !self.config.repeat_test {
                         self.config.last_test_text = None;
                     }
-                    self.config.save().ok();
+                    self.save_config().ok();
                     self.menu_state = MenuState::Typing;
                 }
                 1 => {
                     self.config.end_on_first_error = !self.config.end_on_first_error;
-                    self.config.save().ok();
+                    self.session.end_on_first_error = self.config.end_on_first_error;
+                    self.save_config().ok();
                     self.menu_state = MenuState::Typing;
                 }
                 2 => {
+                    self.config.font_style = self.config.font_style.next();
+                    self.save_config().ok();
+                }
+                3 => {
+                    self.config.caret_style = self.config.caret_style.next();
+                    self.save_config().ok();
+                }
+                4 => {
+                    self.config.use_terminal_cursor = !self.config.use_terminal_cursor;
+                    self.save_config().ok();
+                }
+                5 => {
+                    self.config.pace_caret = self.config.pace_caret.next();
+                    self.save_config().ok();
+                }
+                6 => {
+                    self.menu_state = MenuState::CustomPaceInput(String::new());
+                }
+                7 => {
+                    self.config.key_debounce = self.config.key_debounce.next();
+                    self.save_config().ok();
+                }
+                8 => {
+                    self.config.backspace_mode = self.config.backspace_mode.next();
+                    self.session.backspace_mode = self.config.backspace_mode;
+                    self.save_config().ok();
+                }
+                9 => {
                     self.menu_state = MenuState::MainMenu(0);
                 }
                 _ => {}
             },
 
+            (MenuState::HistoryMenu(idx), KeyCode::Up) => {
+                self.menu_state = MenuState::HistoryMenu(idx.saturating_sub(1));
+            }
+            (MenuState::HistoryMenu(idx), KeyCode::Down) => {
+                let count = self.history.as_ref().map_or(0, |h| h.tests.len());
+                self.menu_state = MenuState::HistoryMenu((idx + 1).min(count.saturating_sub(1)));
+            }
+            (MenuState::HistoryMenu(idx), KeyCode::Enter) => {
+                if let Some(result) = self
+                    .history
+                    .as_ref()
+                    .and_then(|h| h.tests.iter().rev().nth(idx))
+                    .filter(|result| Replay::can_replay(result))
+                {
+                    self.replay = Some(Replay::new(result));
+                    self.menu_state = MenuState::Replay(idx);
+                }
+            }
+            (MenuState::HistoryMenu(_), KeyCode::Esc) => {
+                self.menu_state = MenuState::MainMenu(4);
+            }
+
+            (MenuState::Replay(idx), KeyCode::Esc) => {
+                self.replay = None;
+                self.menu_state = MenuState::HistoryMenu(idx);
+            }
+            (MenuState::Replay(_), code) => {
+                if let Some(replay) = self.replay.as_mut() {
+                    match code {
+                        KeyCode::Char(' ') => replay.toggle_pause(),
+                        KeyCode::Right | KeyCode::Char('l') => replay.step_forward(),
+                        KeyCode::Left | KeyCode::Char('h') => replay.step_back(),
+                        KeyCode::Up | KeyCode::Char('+') => replay.faster(),
+                        KeyCode::Down | KeyCode::Char('-') => replay.slower(),
+                        KeyCode::Char('r') => replay.restart(),
+                        _ => {}
+                    }
+                }
+            }
+
             (MenuState::Help, KeyCode::Up) => {
                 self.help_scroll_offset = self.help_scroll_offset.saturating_sub(1);
             }
@@ -377,17 +728,26 @@ impl App {
                 self.menu_state = MenuState::MainMenu(0);
             }
 
-            (MenuState::TestComplete, KeyCode::Enter) | (MenuState::TestComplete, KeyCode::Esc) => {
+            (MenuState::TestComplete, _)
+                if self.config.keybindings.repeat_text.matches(&key_event) =>
+            {
+                self.retry_seed();
+                self.menu_state = MenuState::Typing;
+            }
+            (MenuState::TestComplete, _)
+                if self.config.keybindings.next_test.matches(&key_event)
+                    || self.config.keybindings.open_menu.matches(&key_event) =>
+            {
                 self.restart_test();
                 self.menu_state = MenuState::Typing;
             }
 
-            (MenuState::CustomTimedInput(ref input), KeyCode::Char(c)) if c.is_numeric() => {
-                if input.len() < 3 {
-                    let mut new_input = input.clone();
-                    new_input.push(c);
-                    self.menu_state = MenuState::CustomTimedInput(new_input);
-                }
+            (MenuState::CustomTimedInput(ref input), KeyCode::Char(c))
+                if c.is_numeric() && input.len() < 3 =>
+            {
+                let mut new_input = input.clone();
+                new_input.push(c);
+                self.menu_state = MenuState::CustomTimedInput(new_input);
             }
             (MenuState::CustomTimedInput(ref input), KeyCode::Backspace) => {
                 let mut new_input = input.clone();
@@ -398,7 +758,6 @@ impl App {
                 if let Ok(seconds) = input.parse::<u32>() {
                     if seconds > 0 {
                         self.config.test_mode = TestMode::Timed(seconds);
-                        self.time_remaining = Some(seconds);
                         self.menu_state = MenuState::Typing;
                         self.restart_test();
                     } else {
@@ -409,12 +768,12 @@ impl App {
                 }
             }
 
-            (MenuState::CustomWordsInput(ref input), KeyCode::Char(c)) if c.is_numeric() => {
-                if input.len() < 3 {
-                    let mut new_input = input.clone();
-                    new_input.push(c);
-                    self.menu_state = MenuState::CustomWordsInput(new_input);
-                }
+            (MenuState::CustomWordsInput(ref input), KeyCode::Char(c))
+                if c.is_numeric() && input.len() < 3 =>
+            {
+                let mut new_input = input.clone();
+                new_input.push(c);
+                self.menu_state = MenuState::CustomWordsInput(new_input);
             }
             (MenuState::CustomWordsInput(ref input), KeyCode::Backspace) => {
                 let mut new_input = input.clone();
@@ -435,6 +794,55 @@ impl App {
                 }
             }
 
+            (MenuState::CustomPaceInput(ref input), KeyCode::Char(c))
+                if c.is_numeric() && input.len() < 3 =>
+            {
+                let mut new_input = input.clone();
+                new_input.push(c);
+                self.menu_state = MenuState::CustomPaceInput(new_input);
+            }
+            (MenuState::CustomPaceInput(ref input), KeyCode::Backspace) => {
+                let mut new_input = input.clone();
+                new_input.pop();
+                self.menu_state = MenuState::CustomPaceInput(new_input);
+            }
+            (MenuState::CustomPaceInput(ref input), KeyCode::Enter) => {
+                if let Ok(wpm) = input.parse::<u32>() {
+                    if wpm > 0 {
+                        self.config.pace_caret_wpm = wpm;
+                        self.config.pace_caret = PaceCaret::Fixed;
+                        self.save_config().ok();
+                        self.menu_state = MenuState::SettingsMenu(5);
+                    } else {
+                        self.menu_state = MenuState::CustomPaceInput(input.clone());
+                    }
+                } else {
+                    self.menu_state = MenuState::SettingsMenu(6);
+                }
+            }
+
+            (MenuState::CommandPalette { .. }, KeyCode::Esc)
+            | (MenuState::LoadFileInput { .. }, KeyCode::Esc) => {
+                self.menu_state = self.resting_state();
+            }
+            (MenuState::CommandPalette { query, selected }, _) => {
+                self.handle_palette_key(query, selected, key_event)?;
+            }
+
+            (MenuState::LoadFileInput { path, .. }, KeyCode::Char(c)) => {
+                self.menu_state = MenuState::LoadFileInput {
+                    path: format!("{path}{c}"),
+                    error: None,
+                };
+            }
+            (MenuState::LoadFileInput { mut path, .. }, KeyCode::Backspace) => {
+                path.pop();
+                self.menu_state = MenuState::LoadFileInput { path, error: None };
+            }
+            (MenuState::LoadFileInput { path, .. }, KeyCode::Enter) => {
+                self.load_text_file(path);
+            }
+
             (_, KeyCode::Esc) => {
                 self.menu_state = MenuState::Typing;
             }
@@ -445,9 +853,167 @@ impl App {
         Ok(())
     }
 
+    pub fn open_command_palette(&mut self) {
+        self.menu_state = MenuState::CommandPalette {
+            query: String::new(),
+            selected: 0,
+        };
+    }
+
+    pub fn palette_results(&self) -> Vec<PaletteCommand> {
+        match &self.menu_state {
+            MenuState::CommandPalette { query, .. } => palette::search(query),
+            _ => Vec::new(),
+        }
+    }
+
+    fn handle_palette_key(
+        &mut self,
+        mut query: String,
+        selected: usize,
+        key_event: crate::input::keys::KeyEvent,
+    ) -> AppResult<()> {
+        use crate::input::keys::{KeyCode, KeyModifiers};
+
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        let count = palette::search(&query).len();
+
+        let selected = match key_event.code {
+            KeyCode::Up | KeyCode::BackTab => selected.saturating_sub(1),
+            KeyCode::Char('p') | KeyCode::Char('k') if ctrl => selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Tab => (selected + 1).min(count.saturating_sub(1)),
+            KeyCode::Char('n') | KeyCode::Char('j') if ctrl => {
+                (selected + 1).min(count.saturating_sub(1))
+            }
+            KeyCode::Enter => {
+                if let Some(command) = palette::search(&query).into_iter().nth(selected) {
+                    self.run_palette_action(command.action)?;
+                }
+                return Ok(());
+            }
+            KeyCode::Backspace => {
+                query.pop();
+                0
+            }
+            KeyCode::Char(c) if !ctrl => {
+                query.push(c);
+                0
+            }
+            _ => selected,
+        };
+
+        self.menu_state = MenuState::CommandPalette { query, selected };
+        Ok(())
+    }
+
+    pub fn run_palette_action(&mut self, action: PaletteAction) -> AppResult<()> {
+        match action {
+            PaletteAction::Menu(target) => {
+                self.menu_state = target.clone();
+                self.handle_menu_keys(Self::create_enter_key_event())?;
+                if self.menu_state == target && self.warning_state == WarningState::None {
+                    self.menu_state = self.resting_state();
+                }
+            }
+            PaletteAction::Restart => {
+                self.restart_test();
+                self.menu_state = MenuState::Typing;
+            }
+            PaletteAction::LoadFile => {
+                self.menu_state = MenuState::LoadFileInput {
+                    path: String::new(),
+                    error: None,
+                };
+            }
+        }
+        Ok(())
+    }
+
+    fn load_text_file(&mut self, path: String) {
+        if !self.can_change_settings("test_mode") {
+            self.set_repeat_mode_warning(
+                "Test mode cannot be changed while Repeat Mode is active.".to_string(),
+            );
+            return;
+        }
+
+        let expanded = match path.strip_prefix("~/").zip(dirs::home_dir()) {
+            Some((rest, home)) => home.join(rest),
+            None => std::path::PathBuf::from(&path),
+        };
+
+        let error = match std::fs::read_to_string(&expanded) {
+            Ok(text) if text.trim().is_empty() => "File is empty".to_string(),
+            Ok(text) => {
+                let words: Vec<&str> = text.split_whitespace().collect();
+                self.config.custom_text = Some(words.join(" "));
+                self.config.test_mode = TestMode::Custom;
+                self.restart_test();
+                self.menu_state = MenuState::Typing;
+                return;
+            }
+            Err(e) => format!("Could not read file: {e}"),
+        };
+
+        self.menu_state = MenuState::LoadFileInput {
+            path,
+            error: Some(error),
+        };
+    }
+
+    fn resting_state(&self) -> MenuState {
+        if self.session.test_complete {
+            MenuState::TestComplete
+        } else {
+            MenuState::Typing
+        }
+    }
+
+    fn is_text_prompt(&self) -> bool {
+        matches!(
+            self.menu_state,
+            MenuState::CommandPalette { .. }
+                | MenuState::LoadFileInput { .. }
+                | MenuState::NewProfileInput { .. }
+        )
+    }
+
+    pub fn take_redraw(&mut self) -> bool {
+        std::mem::take(&mut self.needs_redraw)
+    }
+
     pub fn tick(&mut self) -> AppResult<()> {
-        if self.start_time.is_some() && !self.test_complete {
-            self.update_test_progress();
+        if let Some(race) = self.race.as_mut().and_then(Race::poll) {
+            self.begin_race(race);
+        }
+        if self.race.as_mut().is_some_and(Race::take_updated) {
+            self.needs_redraw = true;
+        }
+
+        if self.session.start_time.is_some() && !self.session.test_complete {
+            self.needs_redraw = true;
+        }
+
+        let now = self.clock.now();
+        self.session.tick(now);
+        self.finish_test_if_complete();
+        self.update_pace();
+
+        if let Some(race) = self.race.as_mut() {
+            let session = &self.session;
+            race.report_progress(
+                session.cursor_pos,
+                session.text_source.len(),
+                session.stats.wpm,
+            );
+        }
+
+        if let Some(replay) = self.replay.as_mut() {
+            let was_paused = replay.paused;
+            replay.update(now);
+            if !was_paused || !replay.paused {
+                self.needs_redraw = true;
+            }
         }
         Ok(())
     }
@@ -455,63 +1021,184 @@ impl App {
     pub fn handle_events(&mut self, event: Event) -> AppResult<()> {
         match event {
             Event::Key(key_event) => self.handle_key_event(key_event),
-            _ => Ok(()),
+            Event::KeyDropped(_) => {
+                self.session.dropped_keys += 1;
+                Ok(())
+            }
+            Event::Mouse(mouse_event) => self.handle_mouse_event(mouse_event),
+            Event::Resize(_, _) => {
+                self.needs_redraw = true;
+                Ok(())
+            }
+            Event::Tick => Ok(()),
+        }
+    }
+
+    pub fn handle_mouse_event(&mut self, mouse_event: MouseEvent) -> AppResult<()> {
+        use crate::input::keys::KeyCode;
+
+        if self.warning_state != WarningState::None {
+            return Ok(());
+        }
+
+        let step = match mouse_event.kind {
+            MouseEventKind::ScrollUp => Some(KeyCode::Up),
+            MouseEventKind::ScrollDown => Some(KeyCode::Down),
+            _ => None,
+        };
+        if let Some(code) = step {
+            if matches!(
+                self.menu_state,
+                MenuState::Help | MenuState::HistoryMenu(_) | MenuState::CommandPalette { .. }
+            ) {
+                self.needs_redraw = true;
+                self.handle_menu_keys(crate::input::keys::KeyEvent::from(code))?;
+            }
+            return Ok(());
+        }
+
+        if mouse_event.kind != MouseEventKind::Down(MouseButton::Left) {
+            return Ok(());
+        }
+
+        let target = self
+            .hit_regions
+            .borrow()
+            .target_at(mouse_event.column, mouse_event.row);
+
+        match target {
+            Some(HitTarget::MenuItem(idx)) if self.menu_state.selected().is_some() => {
+                self.needs_redraw = true;
+                self.menu_state = self.menu_state.with_selected(idx);
+                self.handle_menu_keys(Self::create_enter_key_event())?;
+            }
+            Some(HitTarget::MenuItem(idx))
+                if matches!(self.menu_state, MenuState::CommandPalette { .. }) =>
+            {
+                if let Some(command) = self.palette_results().into_iter().nth(idx) {
+                    self.needs_redraw = true;
+                    self.run_palette_action(command.action)?;
+                }
+            }
+            Some(HitTarget::Restart) if self.menu_state == MenuState::TestComplete => {
+                self.needs_redraw = true;
+                self.restart_test();
+                self.menu_state = MenuState::Typing;
+            }
+            Some(HitTarget::RetrySeed) if self.menu_state == MenuState::TestComplete => {
+                self.needs_redraw = true;
+                self.retry_seed();
+                self.menu_state = MenuState::Typing;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn menu_len(&self) -> Option<usize> {
+        match self.menu_state {
+            MenuState::MainMenu(_) => Some(8),
+            MenuState::TestModeMenu(_) | MenuState::DifficultyMenu(_) => Some(4),
+            MenuState::TimeMenu(_) | MenuState::ThemeMenu(_) => Some(6),
+            MenuState::WordCountMenu(_) => Some(5),
+            MenuState::SettingsMenu(_) => Some(10),
+            MenuState::HistoryMenu(_) => Some(self.history.as_ref().map_or(0, |h| h.tests.len())),
+            MenuState::ProfileMenu(_) => Some(self.profiles.names().len() + 2),
+            _ => None,
+        }
+    }
+
+    fn handle_vim_key(&mut self, key_event: &crate::input::keys::KeyEvent) -> AppResult<bool> {
+        use crate::input::keys::{KeyCode, KeyEvent, KeyModifiers};
+
+        let KeyCode::Char(c) = key_event.code else {
+            return Ok(false);
+        };
+        if key_event
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return Ok(false);
         }
+
+        let help = self.menu_state == MenuState::Help;
+        let Some(len) = self.menu_len().or(help.then_some(0)) else {
+            return Ok(false);
+        };
+
+        let code = match c {
+            'j' => KeyCode::Down,
+            'k' => KeyCode::Up,
+            'h' => KeyCode::Esc,
+            'l' if !help => KeyCode::Enter,
+            'g' if help => {
+                self.help_scroll_offset = 0;
+                return Ok(true);
+            }
+            'G' if help => {
+                self.help_scroll_offset = self.hit_regions.borrow().help_scroll_max;
+                return Ok(true);
+            }
+            'g' => {
+                self.menu_state = self.menu_state.with_selected(0);
+                return Ok(true);
+            }
+            'G' => {
+                self.menu_state = self.menu_state.with_selected(len.saturating_sub(1));
+                return Ok(true);
+            }
+            _ => return Ok(false),
+        };
+
+        self.handle_menu_keys(KeyEvent::new(code, KeyModifiers::NONE))?;
+        Ok(true)
     }
 
     fn handle_numeric_menu_selection(&mut self, digit: u8) -> AppResult<bool> {
         let idx = (digit - 1) as usize;
 
         match self.menu_state.clone() {
-            MenuState::MainMenu(_) => {
-                if idx < 6 {
-                    self.menu_state = MenuState::MainMenu(idx);
+            MenuState::MainMenu(_) if idx < 8 => {
+                self.menu_state = MenuState::MainMenu(idx);
 
-                    self.handle_menu_keys(Self::create_enter_key_event())?;
-                    return Ok(true);
-                }
+                self.handle_menu_keys(Self::create_enter_key_event())?;
+                return Ok(true);
             }
-            MenuState::TestModeMenu(_) => {
-                if idx < 4 {
-                    self.menu_state = MenuState::TestModeMenu(idx);
-                    self.handle_menu_keys(Self::create_enter_key_event())?;
-                    return Ok(true);
-                }
+            MenuState::TestModeMenu(_) if idx < 4 => {
+                self.menu_state = MenuState::TestModeMenu(idx);
+                self.handle_menu_keys(Self::create_enter_key_event())?;
+                return Ok(true);
             }
-            MenuState::DifficultyMenu(_) => {
-                if idx < 4 {
-                    self.menu_state = MenuState::DifficultyMenu(idx);
-                    self.handle_menu_keys(Self::create_enter_key_event())?;
-                    return Ok(true);
-                }
+            MenuState::DifficultyMenu(_) if idx < 4 => {
+                self.menu_state = MenuState::DifficultyMenu(idx);
+                self.handle_menu_keys(Self::create_enter_key_event())?;
+                return Ok(true);
             }
-            MenuState::TimeMenu(_) => {
-                if idx < 6 {
-                    self.menu_state = MenuState::TimeMenu(idx);
-                    self.handle_menu_keys(Self::create_enter_key_event())?;
-                    return Ok(true);
-                }
+            MenuState::TimeMenu(_) if idx < 6 => {
+                self.menu_state = MenuState::TimeMenu(idx);
+                self.handle_menu_keys(Self::create_enter_key_event())?;
+                return Ok(true);
             }
-            MenuState::WordCountMenu(_) => {
-                if idx < 5 {
-                    self.menu_state = MenuState::WordCountMenu(idx);
-                    self.handle_menu_keys(Self::create_enter_key_event())?;
-                    return Ok(true);
-                }
+            MenuState::WordCountMenu(_) if idx < 5 => {
+                self.menu_state = MenuState::WordCountMenu(idx);
+                self.handle_menu_keys(Self::create_enter_key_event())?;
+                return Ok(true);
             }
-            MenuState::ThemeMenu(_) => {
-                if idx < 6 {
-                    self.menu_state = MenuState::ThemeMenu(idx);
-                    self.handle_menu_keys(Self::create_enter_key_event())?;
-                    return Ok(true);
-                }
+            MenuState::ThemeMenu(_) if idx < 6 => {
+                self.menu_state = MenuState::ThemeMenu(idx);
+                self.handle_menu_keys(Self::create_enter_key_event())?;
+                return Ok(true);
             }
-            MenuState::SettingsMenu(_) => {
-                if idx < 3 {
-                    self.menu_state = MenuState::SettingsMenu(idx);
-                    self.handle_menu_keys(Self::create_enter_key_event())?;
-                    return Ok(true);
-                }
+            MenuState::SettingsMenu(_) if idx < 10 => {
+                self.menu_state = MenuState::SettingsMenu(idx);
+                self.handle_menu_keys(Self::create_enter_key_event())?;
+                return Ok(true);
+            }
+            MenuState::ProfileMenu(_) if idx < self.profiles.names().len() + 2 => {
+                self.menu_state = MenuState::ProfileMenu(idx);
+                self.handle_menu_keys(Self::create_enter_key_event())?;
+                return Ok(true);
             }
             _ => {}
         }
@@ -519,46 +1206,71 @@ impl App {
         Ok(false)
     }
 
-    fn create_enter_key_event() -> crossterm::event::KeyEvent {
-        crossterm::event::KeyEvent {
-            code: crossterm::event::KeyCode::Enter,
-            modifiers: crossterm::event::KeyModifiers::empty(),
-            kind: crossterm::event::KeyEventKind::Press,
-            state: crossterm::event::KeyEventState::NONE,
+    fn create_enter_key_event() -> crate::input::keys::KeyEvent {
+        crate::input::keys::KeyEvent {
+            code: crate::input::keys::KeyCode::Enter,
+            modifiers: crate::input::keys::KeyModifiers::empty(),
+            kind: crate::input::keys::KeyEventKind::Press,
+            state: crate::input::keys::KeyEventState::NONE,
         }
     }
 
-    pub fn handle_key_event(&mut self, key_event: crossterm::event::KeyEvent) -> AppResult<()> {
-        use crossterm::event::{KeyCode, KeyModifiers};
+    pub fn handle_key_event(&mut self, key_event: crate::input::keys::KeyEvent) -> AppResult<()> {
+        use crate::input::keys::{KeyCode, KeyModifiers};
+
+        self.needs_redraw = true;
 
         if self.warning_state != WarningState::None && self.handle_warning(key_event) {
             return Ok(());
         }
 
-        let now = Instant::now();
-        let key_code = key_event.code;
+        let now = self.clock.now();
 
-        if let Some(last_press_time) = self.key_timestamps.get(&key_code) {
-            let elapsed = now.duration_since(*last_press_time).as_millis();
+        let bindings = &self.config.keybindings;
 
-            if elapsed < 60 {
-                return Ok(());
-            }
+        if bindings.quit.matches(&key_event) {
+            self.running = false;
+            return Ok(());
         }
 
-        self.key_timestamps.insert(key_code, now);
+        if self.handle_race_key(&key_event) {
+            return Ok(());
+        }
+
+        let bindings = &self.config.keybindings;
+
+        if bindings.toggle_stats.matches(&key_event) {
+            self.show_stats = !self.show_stats;
+            return Ok(());
+        }
 
-        if key_event.code == KeyCode::Char('c')
-            && key_event.modifiers.contains(KeyModifiers::CONTROL)
+        if bindings.command_palette.matches(&key_event) && !self.is_text_prompt() {
+            self.open_command_palette();
+            return Ok(());
+        }
+
+        if key_event.code == KeyCode::Char(':')
+            && match self.menu_state {
+                MenuState::Typing => {
+                    self.session.start_time.is_none()
+                        && self.session.text_source.char_at(self.session.cursor_pos) != Some(':')
+                }
+                MenuState::CustomTimedInput(_)
+                | MenuState::CustomWordsInput(_)
+                | MenuState::CustomPaceInput(_)
+                | MenuState::Replay(_) => false,
+                _ => !self.is_text_prompt(),
+            }
         {
-            self.running = false;
+            self.open_command_palette();
             return Ok(());
         }
 
-        if (key_event.code == KeyCode::Tab)
-            || (key_event.code == KeyCode::Enter && self.test_complete)
+        if !self.is_text_prompt()
+            && (bindings.restart.matches(&key_event)
+                || (bindings.next_test.matches(&key_event) && self.session.test_complete))
         {
-            let elapsed_since_restart = now.duration_since(self.last_restart).as_millis() as u64;
+            let elapsed_since_restart = now.saturating_sub(self.last_restart).as_millis() as u64;
             if elapsed_since_restart < 60 {
                 return Ok(());
             }
@@ -572,200 +1284,62 @@ impl App {
             && self.menu_state != MenuState::Help
             && self.menu_state != MenuState::CustomTimedInput("".into())
             && self.menu_state != MenuState::CustomWordsInput("".into())
+            && self.menu_state != MenuState::CustomPaceInput("".into())
+            && let KeyCode::Char(c) = key_event.code
+            && c.is_ascii_digit()
+            && c != '0'
         {
-            if let KeyCode::Char(c) = key_event.code {
-                if c.is_ascii_digit() && c != '0' {
-                    let digit = c as u8 - b'0';
-                    if self.handle_numeric_menu_selection(digit)? {
-                        return Ok(());
-                    }
-                }
+            let digit = c as u8 - b'0';
+            if self.handle_numeric_menu_selection(digit)? {
+                return Ok(());
             }
         }
 
         if self.menu_state != MenuState::Typing {
+            if self.handle_vim_key(&key_event)? {
+                return Ok(());
+            }
             return self.handle_menu_keys(key_event);
         }
 
-        match key_event.code {
-            KeyCode::Esc => {
-                if self.test_complete {
-                    self.restart_test();
-                    self.menu_state = MenuState::Typing;
-                } else if self.menu_state == MenuState::Typing {
-                    self.menu_state = MenuState::MainMenu(0);
-                } else {
-                    self.menu_state = MenuState::Typing;
-                }
+        if self.config.keybindings.open_menu.matches(&key_event) {
+            if self.session.test_complete {
+                self.restart_test();
+            } else {
+                self.menu_state = MenuState::MainMenu(0);
             }
+            return Ok(());
+        }
 
-            KeyCode::Char(c) if !self.test_complete => {
-                if self.start_time.is_none() {
-                    self.start_time = Some(Instant::now());
-
-                    if let TestMode::Timed(seconds) = self.config.test_mode {
-                        self.time_remaining = Some(seconds);
-                    }
-                }
-
-                if matches!(self.config.test_mode, TestMode::Quote) && self.cursor_pos > 0 {
-                    if self.cursor_pos % 10 == 0 {
-                        let trimmed = self.text_source.trim_text_if_needed(self.cursor_pos);
-                        if trimmed > 0 {
-                            self.cursor_pos -= trimmed;
-                            if trimmed < self.typed_text.len() {
-                                self.typed_text = self.typed_text[trimmed..].to_string();
-                            } else {
-                                self.typed_text.clear();
-                                self.cursor_pos = 0;
-                            }
-                        }
-                    }
-                } else if self.text_source.should_add_more_words(self.cursor_pos) {
-                    let trimmed = self.text_source.add_more_words();
-
-                    if trimmed > 0 && self.cursor_pos > 0 {
-                        if trimmed < self.cursor_pos {
-                            self.cursor_pos -= trimmed;
-
-                            if trimmed < self.typed_text.len() {
-                                self.typed_text = self.typed_text[trimmed..].to_string();
-                            } else {
-                                self.typed_text.clear();
-                                self.cursor_pos = 0;
-                            }
-                        } else {
-                            self.typed_text.clear();
-                            self.cursor_pos = 0;
-                        }
-                    }
-                }
-
-                let target_text = self.text_source.full_text().to_string();
-
-                if c == ' ' {
-                    let is_timed_test = matches!(self.config.test_mode, TestMode::Timed(_));
-                    if !is_timed_test
-                        && self.cursor_pos >= target_text.len()
-                        && !self.text_source.is_scrollable
-                    {
-                        return Ok(());
-                    }
-
-                    if self.config.end_on_first_error
-                        && self.cursor_pos < target_text.len()
-                        && target_text.chars().nth(self.cursor_pos) != Some(' ')
-                    {
-                        self.typed_text.push(' ');
-                        self.cursor_pos += 1;
-
-                        self.stats.update(&self.text_source, &self.typed_text);
-
-                        self.complete_test();
-                        return Ok(());
-                    }
-
-                    if let TestMode::Quote = self.config.test_mode {
-                        if self.cursor_pos < target_text.len() {
-                            let target_char =
-                                target_text.chars().nth(self.cursor_pos).unwrap_or(' ');
-
-                            if target_char == ' ' {
-                                self.typed_text.push(' ');
-                                self.cursor_pos += 1;
-                            } else {
-                                let current_word_end = target_text[self.cursor_pos..]
-                                    .find(' ')
-                                    .map(|i| i + self.cursor_pos)
-                                    .unwrap_or(target_text.len());
-
-                                while self.cursor_pos < current_word_end {
-                                    self.typed_text.push('x');
-                                    self.cursor_pos += 1;
-                                    self.stats.incorrect_chars += 1;
-                                }
-
-                                self.typed_text.push(' ');
-                                self.cursor_pos += 1;
-
-                                if self.config.end_on_first_error {
-                                    self.complete_test();
-                                    return Ok(());
-                                }
-                            }
-                        } else {
-                            if self.text_source.is_complete() {
-                                self.complete_test();
-                                return Ok(());
-                            }
-
-                            if self.text_source.should_add_more_words(self.cursor_pos) {
-                                let _trimmed = self.text_source.add_more_words();
-                                return self.handle_key_event(key_event);
-                            }
-                        }
-                    } else {
-                        if self.cursor_pos < target_text.len() {
-                            let current_word_end = target_text[self.cursor_pos..]
-                                .find(' ')
-                                .map(|i| i + self.cursor_pos)
-                                .unwrap_or(target_text.len());
-
-                            while self.cursor_pos < current_word_end {
-                                let target_char = target_text.chars().nth(self.cursor_pos).unwrap();
-
-                                let error_char = if target_char == 'x' { 'y' } else { 'x' };
-                                self.typed_text.push(error_char);
-                                self.cursor_pos += 1;
-                            }
-                        }
-
-                        self.typed_text.push(' ');
-                        self.cursor_pos += 1;
-                    }
-                } else {
-                    let is_timed_test = matches!(self.config.test_mode, TestMode::Timed(_));
-                    if !is_timed_test && self.cursor_pos >= target_text.len() {
-                        if !self.text_source.is_scrollable || self.text_source.is_complete() {
-                            return Ok(());
-                        }
-
-                        if self.text_source.should_add_more_words(self.cursor_pos) {
-                            self.text_source.add_more_words();
-
-                            let _new_text = self.text_source.full_text().to_string();
-                            return self.handle_key_event(key_event);
-                        }
-                    }
-
-                    if self.config.end_on_first_error && self.cursor_pos < target_text.len() {
-                        let target_char = target_text.chars().nth(self.cursor_pos).unwrap();
-                        if c != target_char {
-                            self.typed_text.push(c);
-                            self.cursor_pos += 1;
-
-                            self.stats.update(&self.text_source, &self.typed_text);
-
-                            self.complete_test();
-                            return Ok(());
-                        }
-                    }
-
-                    self.typed_text.push(c);
-                    self.cursor_pos += 1;
-                }
+        match key_event.code {
+            KeyCode::Backspace
+                if key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.session.delete_word();
+            }
+            KeyCode::Char('w') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.session.delete_word();
+            }
+            KeyCode::Char('h')
+                if key_event.modifiers.contains(KeyModifiers::CONTROL)
+                    && !self.session.test_complete =>
+            {
+                self.session.backspace();
+            }
 
-                if c == ' ' || self.cursor_pos % 5 == 0 {
-                    self.stats.update(&self.text_source, &self.typed_text);
+            KeyCode::Char(c) if !self.session.test_complete => {
+                let was_started = self.session.start_time.is_some();
+                self.session.type_char(c);
+                if !was_started {
+                    self.ghost = Ghost::from_config(&self.config, self.history.as_ref());
                 }
-
-                self.tick()?;
+                self.finish_test_if_complete();
             }
 
-            KeyCode::Backspace if !self.test_complete && !self.typed_text.is_empty() => {
-                self.typed_text.pop();
-                self.cursor_pos = self.cursor_pos.saturating_sub(1);
-                self.stats.update(&self.text_source, &self.typed_text);
+            KeyCode::Backspace if !self.session.test_complete => {
+                self.session.backspace();
             }
 
             _ => {}
@@ -774,99 +1348,168 @@ impl App {
         Ok(())
     }
 
-    fn update_test_progress(&mut self) {
-        if let (Some(start), TestMode::Timed(seconds)) = (self.start_time, self.config.test_mode) {
-            if !self.test_complete {
-                let elapsed = start.elapsed().as_secs() as u32;
-                if elapsed >= seconds {
-                    self.time_remaining = Some(0);
-                    self.complete_test();
-                } else {
-                    self.time_remaining = Some(seconds - elapsed);
-
-                    if self.text_source.should_add_more_words(self.cursor_pos) {
-                        let trimmed = self.text_source.add_more_words();
-
-                        if trimmed > 0 && self.cursor_pos > 0 {
-                            if trimmed < self.cursor_pos {
-                                self.cursor_pos -= trimmed;
-
-                                if trimmed < self.typed_text.len() {
-                                    self.typed_text = self.typed_text[trimmed..].to_string();
-                                } else {
-                                    self.typed_text.clear();
-                                    self.cursor_pos = 0;
-                                }
-                            } else {
-                                self.typed_text.clear();
-                                self.cursor_pos = 0;
-                            }
-                        }
-                    }
-                }
+    fn update_pace(&mut self) {
+        self.pace_pos = match (&self.ghost, self.session.start_time) {
+            (Some(ghost), Some(start)) => {
+                let elapsed_ms = match self.session.end_time {
+                    Some(end) => end.saturating_sub(start).as_millis() as u64,
+                    None => self.clock.now().saturating_sub(start).as_millis() as u64,
+                };
+                Some(ghost.position(elapsed_ms))
             }
-        } else if !matches!(self.config.test_mode, TestMode::Timed(_)) {
-            let is_word_limit_reached = if self.text_source.is_scrollable {
-                self.text_source.is_complete()
-                    && self.typed_text.len() >= self.text_source.full_text().len()
-            } else {
-                self.typed_text.len() >= self.text_source.full_text().len()
-            };
+            _ => None,
+        };
+    }
 
-            if is_word_limit_reached {
-                self.complete_test();
+    fn finish_test_if_complete(&mut self) {
+        if self.session.test_complete && !self.result_recorded {
+            self.result_recorded = true;
+            self.menu_state = MenuState::TestComplete;
+            self.record_result();
+
+            if let Some(race) = self.race.as_mut() {
+                let session = &self.session;
+                race.report_finished(
+                    session.stats.wpm,
+                    session.stats.accuracy,
+                    (session.duration_seconds() * 1000.0) as u64,
+                );
             }
         }
+    }
 
-        if let Some(start) = self.start_time {
-            if !self.test_complete {
-                let elapsed = start.elapsed().as_secs_f64();
-                if elapsed > 0.0 {
-                    self.stats.calculate_wpm(elapsed);
-                }
-            }
+    fn handle_race_key(&mut self, key_event: &crate::input::keys::KeyEvent) -> bool {
+        use crate::input::keys::KeyCode;
+
+        let Some(race) = self.race.as_ref() else {
+            return false;
+        };
+        if !matches!(self.menu_state, MenuState::Typing | MenuState::TestComplete) {
+            return false;
         }
-    }
 
-    fn complete_test(&mut self) {
-        if !self.test_complete {
-            self.test_complete = true;
-            self.end_time = Some(Instant::now());
+        let bindings = &self.config.keybindings;
+        let leaves_results = bindings.next_test.matches(key_event)
+            || bindings.open_menu.matches(key_event)
+            || bindings.repeat_text.matches(key_event);
 
-            if self.config.end_on_first_error && self.stats.incorrect_chars > 0 {
-                self.test_end_reason = Some("Test ended due to typing error".to_string());
+        match key_event.code {
+            KeyCode::Enter if race.is_host() && race.phase != RacePhase::Racing => {
+                self.start_race();
+                true
             }
+            _ if bindings.restart.matches(key_event) => true,
+            _ if leaves_results => self.menu_state == MenuState::TestComplete,
+            KeyCode::Char(_) | KeyCode::Backspace => race.phase != RacePhase::Racing,
+            _ => false,
+        }
+    }
 
-            self.menu_state = MenuState::TestComplete;
-            self.stats.finalize();
+    fn start_race(&mut self) {
+        let settings = RaceSettings {
+            seed: self.seed.unwrap_or_else(random_seed),
+            test_mode: self.config.test_mode,
+            difficulty: self.config.difficulty,
+            custom_text: self.config.custom_text.clone(),
+            word_list: self.config.word_list.clone(),
+        };
+
+        if let Some(race) = self.race.as_mut() {
+            race.start(settings);
         }
     }
 
+    fn begin_race(&mut self, race: RaceSettings) {
+        let config = Config {
+            test_mode: race.test_mode,
+            difficulty: race.difficulty,
+            custom_text: race.custom_text,
+            word_list: race.word_list,
+            repeat_test: false,
+            last_test_text: None,
+            seed: Some(race.seed),
+            ..self.config.clone()
+        };
+
+        let text_source = TextSource::with_seed(&config, config.seed);
+        self.session = TypingSession::with_text_source(&config, text_source, self.clock.clone());
+        self.result_recorded = false;
+        self.ghost = None;
+        self.pace_pos = None;
+        self.menu_state = MenuState::Typing;
+    }
+
+    fn record_result(&mut self) {
+        let storage = self.profile_storage();
+        let Some(history) = self.history.as_mut() else {
+            return;
+        };
+
+        let session = &self.session;
+        history.add_result(TestResult {
+            timestamp: self.clock.wall_time(),
+            duration: session.duration_seconds(),
+            wpm: session.stats.wpm,
+            accuracy: session.stats.accuracy,
+            mode: test_mode_name(session.test_mode),
+            text: session.text_source.full_text().to_string(),
+            keystrokes: session.keystrokes.clone(),
+        });
+        history.save_to(&*storage).ok();
+    }
+
     pub fn restart_test(&mut self) {
+        self.restart_with_seed(self.seed);
+    }
+
+    pub fn retry_seed(&mut self) {
+        let seed = self.session.text_source.seed();
+        self.restart_with_seed(Some(seed));
+    }
+
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+        self.restart_with_seed(seed);
+    }
+
+    fn restart_with_seed(&mut self, seed: Option<u64>) {
+        if self
+            .race
+            .as_ref()
+            .is_some_and(|race| race.phase == RacePhase::Racing)
+        {
+            return;
+        }
+
         if self.config.repeat_test
             && self.config.last_test_text.is_none()
-            && !self.text_source.full_text().is_empty()
+            && !self.session.text_source.is_empty()
         {
-            self.config.last_test_text = Some(self.text_source.full_text().to_string());
-            self.config.save().ok();
+            self.config.last_test_text = Some(self.session.text_source.full_text().to_string());
+            self.save_config().ok();
         }
 
         if matches!(self.config.test_mode, TestMode::Quote) {
             self.config.last_test_text = None;
         }
 
-        self.text_source = TextSource::new(&self.config);
-        self.typed_text.clear();
-        self.cursor_pos = 0;
-        self.stats = TypingStats::default();
-        self.start_time = None;
-        self.end_time = None;
-        self.key_timestamps.clear();
-        self.test_complete = false;
-        self.test_end_reason = None;
+        let text_source = TextSource::with_seed(&self.config, seed);
+        self.session =
+            TypingSession::with_text_source(&self.config, text_source, self.clock.clone());
+        self.result_recorded = false;
+        self.ghost = None;
+        self.pace_pos = None;
+    }
 
-        if let TestMode::Timed(seconds) = self.config.test_mode {
-            self.time_remaining = Some(seconds);
+    pub fn terminal_cursor_style(&self) -> Option<CaretStyle> {
+        if self.config.use_terminal_cursor
+            && self.config.caret_style != CaretStyle::Off
+            && self.menu_state == MenuState::Typing
+            && self.warning_state == WarningState::None
+        {
+            Some(self.config.caret_style)
+        } else {
+            None
         }
     }
 
@@ -893,8 +1536,8 @@ impl App {
         self.warning_state = WarningState::RepeatModeSettings { action, prev_state };
     }
 
-    fn handle_warning(&mut self, key_event: crossterm::event::KeyEvent) -> bool {
-        use crossterm::event::KeyCode;
+    fn handle_warning(&mut self, key_event: crate::input::keys::KeyEvent) -> bool {
+        use crate::input::keys::KeyCode;
 
         match &self.warning_state {
             WarningState::None => false,
@@ -906,7 +1549,7 @@ impl App {
                 KeyCode::Enter => {
                     self.config.repeat_test = false;
                     self.config.last_test_text = None;
-                    self.config.save().ok();
+                    self.save_config().ok();
 
                     self.menu_state = (**prev_state).clone();
 
@@ -923,6 +1566,13 @@ impl App {
 
                 _ => true,
             },
+
+            WarningState::ConfigRecovered { .. } => {
+                if matches!(key_event.code, KeyCode::Enter | KeyCode::Esc) {
+                    self.warning_state = WarningState::None;
+                }
+                true
+            }
         }
     }
 }
@@ -0,0 +1,107 @@
+use crate::ui::MenuState;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteAction {
+    Menu(MenuState),
+
+    Restart,
+
+    LoadFile,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteCommand {
+    pub label: &'static str,
+
+    pub action: PaletteAction,
+}
+
+fn menu(label: &'static str, state: MenuState) -> PaletteCommand {
+    PaletteCommand {
+        label,
+        action: PaletteAction::Menu(state),
+    }
+}
+
+pub fn commands() -> Vec<PaletteCommand> {
+    vec![
+        menu("Mode: Timed 15 seconds", MenuState::TimeMenu(0)),
+        menu("Mode: Timed 30 seconds", MenuState::TimeMenu(1)),
+        menu("Mode: Timed 60 seconds", MenuState::TimeMenu(2)),
+        menu("Mode: Timed 120 seconds", MenuState::TimeMenu(3)),
+        menu("Mode: Timed custom...", MenuState::TimeMenu(4)),
+        menu("Mode: Words 10", MenuState::WordCountMenu(0)),
+        menu("Mode: Words 25", MenuState::WordCountMenu(1)),
+        menu("Mode: Words 50", MenuState::WordCountMenu(2)),
+        menu("Mode: Words custom...", MenuState::WordCountMenu(3)),
+        menu("Mode: Quote", MenuState::TestModeMenu(2)),
+        PaletteCommand {
+            label: "Mode: Load text from file...",
+            action: PaletteAction::LoadFile,
+        },
+        menu("Difficulty: Easy", MenuState::DifficultyMenu(0)),
+        menu("Difficulty: Medium", MenuState::DifficultyMenu(1)),
+        menu("Difficulty: Hard", MenuState::DifficultyMenu(2)),
+        menu("Theme: Light", MenuState::ThemeMenu(0)),
+        menu("Theme: Dark", MenuState::ThemeMenu(1)),
+        menu("Theme: Sepia", MenuState::ThemeMenu(2)),
+        menu("Theme: Matrix", MenuState::ThemeMenu(3)),
+        menu("Theme: Ocean", MenuState::ThemeMenu(4)),
+        menu("Toggle repeat mode", MenuState::SettingsMenu(0)),
+        menu("Toggle end on first error", MenuState::SettingsMenu(1)),
+        menu("Cycle font style", MenuState::SettingsMenu(2)),
+        menu("Cycle caret style", MenuState::SettingsMenu(3)),
+        menu("Toggle terminal cursor", MenuState::SettingsMenu(4)),
+        menu("Cycle pace caret", MenuState::SettingsMenu(5)),
+        menu("Set pace caret WPM...", MenuState::SettingsMenu(6)),
+        menu("Cycle key debounce", MenuState::SettingsMenu(7)),
+        menu("Cycle backspace mode", MenuState::SettingsMenu(8)),
+        menu("Open settings", MenuState::MainMenu(3)),
+        menu("Open history", MenuState::MainMenu(4)),
+        menu("Open help", MenuState::MainMenu(5)),
+        menu("Switch profile", MenuState::MainMenu(6)),
+        PaletteCommand {
+            label: "Restart test",
+            action: PaletteAction::Restart,
+        },
+    ]
+}
+
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut last_match: Option<usize> = None;
+
+    for q in query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+    {
+        let found = next + candidate[next..].iter().position(|&c| c == q)?;
+
+        score += 1;
+        if last_match.is_some_and(|last| last + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !candidate[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (found - next) as i64;
+
+        last_match = Some(found);
+        next = found + 1;
+    }
+
+    Some(score)
+}
+
+pub fn search(query: &str) -> Vec<PaletteCommand> {
+    let mut scored: Vec<(i64, PaletteCommand)> = commands()
+        .into_iter()
+        .filter_map(|command| fuzzy_score(query, command.label).map(|score| (score, command)))
+        .collect();
+
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, command)| command).collect()
+}
@@ -1,126 +1,362 @@
-use crate::{App, AppResult, Config};
+use crate::config::{Difficulty, TestMode};
+use crate::input::keys::{
+    KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use crate::storage::{LocalStorage, SharedStorage, default_storage};
+use crate::ui::{BufferExport, MenuState, ThemeType, render_to_buffer};
+use crate::{App, Config};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
+use web_sys::KeyboardEvent;
+
+type KeyQueue = Rc<RefCell<VecDeque<KeyEvent>>>;
+
+#[wasm_bindgen(start)]
+pub fn start() {
+    console_error_panic_hook::set_once();
+}
+
+pub fn key_event(key: &str, ctrl: bool, alt: bool, shift: bool) -> Option<KeyEvent> {
+    let code = match key {
+        "Enter" => KeyCode::Enter,
+        "Escape" | "Esc" => KeyCode::Esc,
+        "Tab" if shift => KeyCode::BackTab,
+        "Tab" => KeyCode::Tab,
+        "Backspace" => KeyCode::Backspace,
+        "Delete" => KeyCode::Delete,
+        "Insert" => KeyCode::Insert,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        "ArrowUp" => KeyCode::Up,
+        "ArrowDown" => KeyCode::Down,
+        "ArrowLeft" => KeyCode::Left,
+        "ArrowRight" => KeyCode::Right,
+        "Spacebar" => KeyCode::Char(' '),
+        f if f.len() > 1 && f.starts_with('F') => KeyCode::F(f[1..].parse().ok()?),
+        s => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => return None,
+            }
+        }
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    if ctrl {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+    if alt {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if shift {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+
+    Some(KeyEvent::new(code, modifiers))
+}
+
+fn js_error(err: anyhow::Error) -> JsError {
+    JsError::new(&format!("{err:#}"))
+}
 
 #[wasm_bindgen]
 pub struct WasmApp {
     app: App,
+    keys: KeyQueue,
+    listener: Option<Closure<dyn FnMut(KeyboardEvent)>>,
+}
+
+impl Default for WasmApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WasmApp {
+    pub fn with_storage(storage: SharedStorage) -> Self {
+        let mut app = App::new(Config::default());
+        app.set_storage(storage);
+        let warnings = app
+            .load_profile(None)
+            .unwrap_or_else(|err| vec![format!("Could not load your profile: {err:#}")]);
+        app.show_config_warnings(warnings);
+
+        Self {
+            app,
+            keys: KeyQueue::default(),
+            listener: None,
+        }
+    }
+
+    fn restart_and_save(&mut self) {
+        self.app.save_config().ok();
+        self.restart();
+    }
+
+    fn mouse(&mut self, kind: MouseEventKind, column: u16, row: u16) -> Result<(), JsError> {
+        self.app
+            .handle_mouse_event(MouseEvent {
+                kind,
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            })
+            .map_err(js_error)?;
+        self.app.tick().map_err(js_error)
+    }
 }
 
 #[wasm_bindgen]
 impl WasmApp {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        console_error_panic_hook::set_once();
+        let mut wasm = Self::with_storage(default_storage());
+        if !LocalStorage::is_available() {
+            wasm.app.show_notice(
+                "Browser storage is unavailable, so settings and results won't be saved.",
+            );
+        }
+        wasm
+    }
+
+    pub fn key_down(
+        &mut self,
+        key: &str,
+        ctrl: bool,
+        alt: bool,
+        shift: bool,
+    ) -> Result<bool, JsError> {
+        let Some(event) = key_event(key, ctrl, alt, shift) else {
+            return Ok(false);
+        };
+
+        self.app.handle_key_event(event).map_err(js_error)?;
+        self.app.tick().map_err(js_error)?;
+        Ok(true)
+    }
 
-        let config = Config::default();
-        let app = App::new(config);
+    pub fn key_press(&mut self, key: &str) -> Result<bool, JsError> {
+        self.key_down(key, false, false, false)
+    }
 
-        Self { app }
+    pub fn type_text(&mut self, text: &str) -> Result<(), JsError> {
+        for c in text.chars() {
+            self.key_down(&c.to_string(), false, false, c.is_uppercase())?;
+        }
+        Ok(())
     }
 
-    #[wasm_bindgen]
-    pub fn key_press(&mut self, key: &str) -> bool {
-        let key_event = convert_key_to_event(key);
+    pub fn listen(&mut self) -> Result<(), JsError> {
+        if self.listener.is_some() {
+            return Ok(());
+        }
 
-        if let Some(key_event) = key_event {
-            if let Ok(_) = self.app.handle_key_event(key_event) {
-                return true;
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| JsError::new("no document to listen on"))?;
+
+        let keys = self.keys.clone();
+        let listener = Closure::<dyn FnMut(KeyboardEvent)>::new(move |event: KeyboardEvent| {
+            if event.meta_key() {
+                return;
+            }
+            if let Some(key) = key_event(
+                &event.key(),
+                event.ctrl_key(),
+                event.alt_key(),
+                event.shift_key(),
+            ) {
+                event.prevent_default();
+                keys.borrow_mut().push_back(key);
             }
+        });
+
+        document
+            .add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref())
+            .map_err(|_| JsError::new("could not add a keydown listener"))?;
+        self.listener = Some(listener);
+        Ok(())
+    }
+
+    pub fn tick(&mut self) -> Result<(), JsError> {
+        let pending: Vec<KeyEvent> = self.keys.borrow_mut().drain(..).collect();
+        for key in pending {
+            self.app.handle_key_event(key).map_err(js_error)?;
         }
-        false
+        self.app.tick().map_err(js_error)
+    }
+
+    pub fn render(&self, width: u16, height: u16) -> Result<String, JsError> {
+        let buffer = render_to_buffer(&self.app, width, height).map_err(js_error)?;
+        serde_json::to_string(&BufferExport::from(&buffer)).map_err(|err| js_error(err.into()))
     }
 
-    #[wasm_bindgen]
-    pub fn get_wpm(&self) -> f64 {
-        self.app.stats.wpm
+    pub fn click(&mut self, column: u16, row: u16) -> Result<(), JsError> {
+        self.mouse(MouseEventKind::Down(MouseButton::Left), column, row)
     }
 
-    #[wasm_bindgen]
-    pub fn get_accuracy(&self) -> f64 {
-        self.app.stats.accuracy
+    pub fn scroll(&mut self, column: u16, row: u16, up: bool) -> Result<(), JsError> {
+        let kind = if up {
+            MouseEventKind::ScrollUp
+        } else {
+            MouseEventKind::ScrollDown
+        };
+        self.mouse(kind, column, row)
     }
 
-    #[wasm_bindgen]
-    pub fn get_target_text(&self) -> String {
-        self.app.text_source.full_text().to_string()
+    pub fn is_running(&self) -> bool {
+        self.app.running
     }
 
-    #[wasm_bindgen]
-    pub fn get_typed_text(&self) -> String {
-        self.app.typed_text.clone()
+    pub fn screen(&self) -> String {
+        match self.app.menu_state {
+            MenuState::Typing => "typing",
+            MenuState::TestComplete => "results",
+            _ => "menu",
+        }
+        .to_string()
     }
 
-    #[wasm_bindgen]
-    pub fn get_cursor_position(&self) -> usize {
-        self.app.cursor_pos
+    pub fn snapshot(&self) -> String {
+        serde_json::to_string(&self.app.session.snapshot()).unwrap_or_default()
     }
 
-    #[wasm_bindgen]
-    pub fn is_test_complete(&self) -> bool {
-        self.app.test_complete
+    pub fn wpm(&self) -> f64 {
+        self.app.session.stats.wpm
     }
 
-    #[wasm_bindgen]
-    pub fn restart_test(&mut self) {
-        self.app.restart_test();
+    pub fn raw_wpm(&self) -> f64 {
+        self.app.session.stats.raw_wpm
     }
 
-    #[wasm_bindgen]
-    pub fn set_test_mode(&mut self, mode_type: u8, value: u32) {
-        use crate::config::TestMode;
+    pub fn accuracy(&self) -> f64 {
+        self.app.session.stats.accuracy
+    }
 
-        self.app.config.test_mode = match mode_type {
-            0 => TestMode::Timed(value),
-            1 => TestMode::Words(value),
-            2 => TestMode::Quote,
-            3 => TestMode::Custom,
-            _ => TestMode::Timed(60),
-        };
+    pub fn text(&self) -> String {
+        self.app.session.text_source.full_text().to_string()
+    }
+
+    pub fn typed_text(&self) -> String {
+        self.app.session.typed_text()
+    }
+
+    pub fn cursor_position(&self) -> usize {
+        self.app.session.cursor_pos
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.app.session.start_time.is_some()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.app.session.test_complete
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.app.session.text_source.seed()
+    }
+
+    pub fn profile(&self) -> String {
+        self.app.profile().to_string()
+    }
+
+    pub fn set_profile(&mut self, name: &str) -> Result<(), JsError> {
+        let warnings = self.app.load_profile(Some(name)).map_err(js_error)?;
+        self.app.menu_state = MenuState::Typing;
+        self.app.show_config_warnings(warnings);
+        Ok(())
+    }
 
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.app.set_seed(seed);
+        self.app.menu_state = MenuState::Typing;
+    }
+
+    pub fn restart(&mut self) {
         self.app.restart_test();
+        self.app.menu_state = MenuState::Typing;
     }
 
-    #[wasm_bindgen]
-    pub fn set_theme(&mut self, theme_type: u8) {
-        use crate::ui::ThemeType;
+    pub fn retry_seed(&mut self) {
+        self.app.retry_seed();
+        self.app.menu_state = MenuState::Typing;
+    }
+
+    pub fn set_test_mode(&mut self, mode: &str, value: Option<u32>) -> Result<(), JsError> {
+        self.app.config.test_mode = match mode {
+            "timed" => TestMode::Timed(value.unwrap_or(60)),
+            "words" => TestMode::Words(value.unwrap_or(50)),
+            "quote" => TestMode::Quote,
+            "custom" => TestMode::Custom,
+            _ => return Err(JsError::new(&format!("unknown test mode '{mode}'"))),
+        };
+        self.restart_and_save();
+        Ok(())
+    }
 
-        let theme = match theme_type {
-            0 => ThemeType::Light,
-            1 => ThemeType::Dark,
-            2 => ThemeType::Sepia,
-            3 => ThemeType::Matrix,
-            4 => ThemeType::Ocean,
-            _ => ThemeType::Dark,
+    pub fn set_difficulty(&mut self, difficulty: &str) -> Result<(), JsError> {
+        self.app.config.difficulty = match difficulty {
+            "easy" => Difficulty::Easy,
+            "medium" => Difficulty::Medium,
+            "hard" => Difficulty::Hard,
+            _ => return Err(JsError::new(&format!("unknown difficulty '{difficulty}'"))),
         };
+        self.restart_and_save();
+        Ok(())
+    }
 
-        self.app.set_theme(theme);
+    pub fn set_custom_text(&mut self, text: &str) {
+        self.app.config.custom_text = Some(text.to_string());
+        self.app.config.test_mode = TestMode::Custom;
+        self.restart_and_save();
     }
 
-    #[wasm_bindgen]
-    pub fn set_custom_text(&mut self, text: String) {
-        self.app.config.custom_text = Some(text);
-        self.app.restart_test();
+    pub fn set_theme(&mut self, theme: &str) -> Result<(), JsError> {
+        let theme_type = match theme {
+            "light" => ThemeType::Light,
+            "dark" => ThemeType::Dark,
+            "sepia" => ThemeType::Sepia,
+            "matrix" => ThemeType::Matrix,
+            "ocean" => ThemeType::Ocean,
+            _ => return Err(JsError::new(&format!("unknown theme '{theme}'"))),
+        };
+        self.app.set_theme(theme_type);
+        self.app.save_config().ok();
+        Ok(())
     }
-}
 
-fn convert_key_to_event(key: &str) -> Option<crossterm::event::KeyEvent> {
-    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-
-    match key {
-        "Escape" => Some(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty())),
-        "Tab" => Some(KeyEvent::new(KeyCode::Tab, KeyModifiers::empty())),
-        "Backspace" => Some(KeyEvent::new(KeyCode::Backspace, KeyModifiers::empty())),
-        "Enter" => Some(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty())),
-        "Space" => Some(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::empty())),
-        s if s.len() == 1 => {
-            let ch = s.chars().next().unwrap();
-            Some(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::empty()))
-        }
-        _ => None,
+    pub fn theme_colors(&self) -> Vec<u8> {
+        let theme = &self.app.theme;
+        [
+            theme.background,
+            theme.text,
+            theme.accent,
+            theme.correct,
+            theme.incorrect,
+            theme.pending,
+            theme.cursor,
+        ]
+        .iter()
+        .flat_map(|&(r, g, b)| [r, g, b])
+        .collect()
     }
 }
 
-#[wasm_bindgen(start)]
-pub fn start() {
-    web_sys::console::log_1(&JsValue::from_str("TuiType WASM module initialized"));
+impl Drop for WasmApp {
+    fn drop(&mut self) {
+        if let Some(listener) = self.listener.take()
+            && let Some(document) = web_sys::window().and_then(|window| window.document())
+        {
+            document
+                .remove_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref())
+                .ok();
+        }
+    }
 }
@@ -0,0 +1,89 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::{HEIGHT, Harness, WIDTH};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use tuitype::ui::{BufferExport, css_color, render_to_buffer};
+
+#[test]
+fn rendered_buffer_matches_the_screen() {
+    let harness = Harness::custom("the quick brown fox");
+    let buffer = render_to_buffer(&harness.app, WIDTH, HEIGHT).unwrap();
+    let export = BufferExport::from(&buffer);
+
+    assert_eq!((export.width, export.height), (WIDTH, HEIGHT));
+    assert_eq!(export.cells.len(), WIDTH as usize * HEIGHT as usize);
+
+    let screen: Vec<String> = export
+        .cells
+        .chunks(WIDTH as usize)
+        .map(|row| {
+            let line: String = row.iter().map(|cell| cell.symbol.as_str()).collect();
+            line.trim_end().to_string()
+        })
+        .collect();
+    assert_eq!(screen.join("\n") + "\n", harness.render(WIDTH, HEIGHT));
+}
+
+#[test]
+fn typed_text_is_exported_in_theme_colors() {
+    let mut harness = Harness::custom("the quick brown fox");
+    harness.type_text("tha");
+
+    let buffer = render_to_buffer(&harness.app, WIDTH, HEIGHT).unwrap();
+    let export = BufferExport::from(&buffer);
+    let theme = &harness.app.theme;
+    let hex = |(r, g, b): (u8, u8, u8)| Some(format!("#{r:02x}{g:02x}{b:02x}"));
+
+    let row = export
+        .cells
+        .chunks(WIDTH as usize)
+        .find(|row| {
+            let line: String = row.iter().map(|cell| cell.symbol.as_str()).collect();
+            line.contains("the quick")
+        })
+        .expect("text row is rendered");
+    let start = row
+        .windows(3)
+        .position(|w| w.iter().map(|c| c.symbol.as_str()).collect::<String>() == "the")
+        .unwrap();
+
+    assert_eq!(row[start].fg, hex(theme.correct));
+    assert_eq!(row[start + 2].fg, hex(theme.incorrect));
+}
+
+#[test]
+fn colors_and_modifiers_are_exported_for_css() {
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 1));
+    buffer.set_string(
+        0,
+        0,
+        "ab",
+        Style::default()
+            .fg(Color::Red)
+            .bg(Color::Rgb(1, 2, 255))
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+    );
+    buffer.set_string(2, 0, "c", Style::default().fg(Color::Indexed(196)));
+
+    let export = BufferExport::from(&buffer);
+    let a = &export.cells[0];
+    assert_eq!(a.symbol, "a");
+    assert_eq!(a.fg.as_deref(), Some("#cd0000"));
+    assert_eq!(a.bg.as_deref(), Some("#0102ff"));
+    assert_eq!(a.modifiers, ["bold", "underlined"]);
+
+    let c = &export.cells[2];
+    assert_eq!(c.fg.as_deref(), Some("#ff0000"));
+    assert_eq!(c.bg, None);
+    assert!(c.modifiers.is_empty());
+
+    assert_eq!(css_color(Color::Indexed(244)).as_deref(), Some("#808080"));
+    assert_eq!(css_color(Color::Reset), None);
+
+    let json = serde_json::to_value(&export).unwrap();
+    assert_eq!(json["cells"][0]["modifiers"][1], "underlined");
+}
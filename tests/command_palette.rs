@@ -0,0 +1,149 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::{HEIGHT, Harness, WIDTH};
+use crossterm::event::{KeyCode, KeyModifiers};
+use tuitype::config::{Config, Difficulty, TestMode};
+use tuitype::ui::{MenuState, PaletteAction, ThemeType};
+
+fn run(harness: &mut Harness, query: &str) {
+    harness
+        .press_with(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .type_text(query)
+        .press(KeyCode::Enter);
+}
+
+#[test]
+fn fuzzy_search_ranks_the_closest_command_first() {
+    let mut harness = Harness::custom("alpha beta");
+    harness.type_text(":time30");
+
+    let results = harness.app.palette_results();
+    assert_eq!(results[0].label, "Mode: Timed 30 seconds");
+    assert!(results.iter().all(|c| c.label.contains('3')));
+
+    harness.press(KeyCode::Backspace).press(KeyCode::Backspace);
+    harness.type_text("zzz");
+    assert!(harness.app.palette_results().is_empty());
+    assert!(
+        harness
+            .render(WIDTH, HEIGHT)
+            .contains("No matching commands")
+    );
+}
+
+#[test]
+fn palette_switches_presets_in_one_step() {
+    let mut harness = Harness::custom("alpha beta");
+
+    run(&mut harness, "timed 60");
+    assert_eq!(harness.app.config.test_mode, TestMode::Timed(60));
+    assert_eq!(harness.app.menu_state, MenuState::Typing);
+
+    run(&mut harness, "words 25");
+    assert_eq!(harness.app.config.test_mode, TestMode::Words(25));
+
+    run(&mut harness, "hard");
+    assert_eq!(harness.app.config.difficulty, Difficulty::Hard);
+
+    run(&mut harness, "matrix");
+    assert_eq!(harness.app.config.theme_type, ThemeType::Matrix);
+
+    run(&mut harness, "end on err");
+    assert!(harness.app.config.end_on_first_error);
+
+    run(&mut harness, "history");
+    assert_eq!(harness.app.menu_state, MenuState::HistoryMenu(0));
+}
+
+#[test]
+fn cycling_settings_returns_to_the_test() {
+    let mut harness = Harness::custom("alpha beta");
+    let font = harness.app.config.font_style;
+
+    run(&mut harness, "font");
+    assert_eq!(harness.app.config.font_style, font.next());
+    assert_eq!(harness.app.menu_state, MenuState::Typing);
+}
+
+#[test]
+fn colon_only_opens_the_palette_before_typing() {
+    let mut harness = Harness::custom("a:b c");
+    harness.type_text("a:");
+    assert_eq!(harness.app.menu_state, MenuState::Typing);
+    assert_eq!(harness.app.session.typed_text(), "a:");
+
+    harness.press_with(KeyCode::Char('p'), KeyModifiers::CONTROL);
+    assert!(matches!(
+        harness.app.menu_state,
+        MenuState::CommandPalette { .. }
+    ));
+    harness.press(KeyCode::Esc);
+    assert_eq!(harness.app.menu_state, MenuState::Typing);
+    assert_eq!(harness.app.session.typed_text(), "a:");
+}
+
+#[test]
+fn colon_is_typed_when_the_text_starts_with_one() {
+    let mut harness = Harness::custom(":wq now");
+    harness.type_text(":w");
+    assert_eq!(harness.app.menu_state, MenuState::Typing);
+    assert_eq!(harness.app.session.typed_text(), ":w");
+}
+
+#[test]
+fn selection_moves_with_arrows_and_mouse() {
+    let mut harness = Harness::custom("alpha beta");
+    harness.type_text(":theme");
+    harness.keys(&[KeyCode::Down, KeyCode::Down, KeyCode::Up]);
+    assert!(matches!(
+        harness.app.menu_state,
+        MenuState::CommandPalette { selected: 1, .. }
+    ));
+
+    let screen = harness.render(WIDTH, HEIGHT);
+    let row = screen
+        .lines()
+        .position(|line| line.contains("Theme: Sepia"))
+        .expect("Sepia is listed") as u16;
+    harness.click(40, row);
+    assert_eq!(harness.app.config.theme_type, ThemeType::Sepia);
+}
+
+#[test]
+fn load_file_replaces_the_text() {
+    let path = std::env::temp_dir().join(format!("tuitype-palette-{}.txt", std::process::id()));
+    std::fs::write(&path, "first line\nsecond   line\n").unwrap();
+
+    let mut harness = Harness::new(Config::default());
+    harness.type_text(":load");
+    assert_eq!(
+        harness.app.palette_results()[0].action,
+        PaletteAction::LoadFile
+    );
+    harness.press(KeyCode::Enter);
+
+    harness
+        .type_text("/nonexistent/tuitype.txt")
+        .press(KeyCode::Enter);
+    assert!(matches!(
+        &harness.app.menu_state,
+        MenuState::LoadFileInput { error: Some(_), .. }
+    ));
+
+    for _ in 0.."/nonexistent/tuitype.txt".len() {
+        harness.press(KeyCode::Backspace);
+    }
+    harness
+        .type_text(&path.display().to_string())
+        .press(KeyCode::Enter);
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(harness.app.menu_state, MenuState::Typing);
+    assert_eq!(harness.app.config.test_mode, TestMode::Custom);
+    assert_eq!(
+        harness.app.session.text_source.full_text(),
+        "first line second line"
+    );
+}
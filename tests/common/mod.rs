@@ -0,0 +1,160 @@
+#![allow(dead_code)]
+
+use chrono::FixedOffset;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tuitype::clock::ManualClock;
+use tuitype::config::{Config, TestMode};
+use tuitype::storage::MemoryStorage;
+use tuitype::ui::{App, render_to_buffer};
+
+pub const WIDTH: u16 = 100;
+pub const HEIGHT: u16 = 30;
+
+pub const SEED: u64 = 42;
+
+const KEY_INTERVAL: Duration = Duration::from_millis(100);
+
+pub struct Harness {
+    pub app: App,
+
+    pub clock: Arc<ManualClock>,
+
+    pub storage: Arc<MemoryStorage>,
+}
+
+impl Harness {
+    pub fn new(mut config: Config) -> Self {
+        config.seed.get_or_insert(SEED);
+
+        let clock = Arc::new(ManualClock::new());
+        let storage = Arc::new(MemoryStorage::new());
+        let mut app = App::with_clock(config, clock.clone());
+        app.set_storage(storage.clone());
+        app.set_utc_offset(FixedOffset::east_opt(0).unwrap());
+
+        Self {
+            app,
+            clock,
+            storage,
+        }
+    }
+
+    pub fn custom(text: &str) -> Self {
+        let config = Config {
+            test_mode: TestMode::Custom,
+            custom_text: Some(text.to_string()),
+            ..Config::default()
+        };
+
+        Self::new(config)
+    }
+
+    pub fn press(&mut self, code: KeyCode) -> &mut Self {
+        self.press_with(code, KeyModifiers::empty())
+    }
+
+    pub fn press_with(&mut self, code: KeyCode, modifiers: KeyModifiers) -> &mut Self {
+        self.clock.advance(KEY_INTERVAL);
+        self.app
+            .handle_key_event(KeyEvent::new(code, modifiers))
+            .expect("key event failed");
+        self.app.tick().expect("tick failed");
+        self
+    }
+
+    pub fn keys(&mut self, codes: &[KeyCode]) -> &mut Self {
+        for &code in codes {
+            self.press(code);
+        }
+        self
+    }
+
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        for c in text.chars() {
+            self.press(KeyCode::Char(c));
+        }
+        self
+    }
+
+    pub fn mouse(&mut self, kind: MouseEventKind, column: u16, row: u16) -> &mut Self {
+        self.clock.advance(KEY_INTERVAL);
+        self.app
+            .handle_mouse_event(MouseEvent {
+                kind,
+                column,
+                row,
+                modifiers: KeyModifiers::empty(),
+            })
+            .expect("mouse event failed");
+        self.app.tick().expect("tick failed");
+        self
+    }
+
+    pub fn click(&mut self, column: u16, row: u16) -> &mut Self {
+        self.mouse(MouseEventKind::Down(MouseButton::Left), column, row)
+    }
+
+    pub fn advance(&mut self, by: Duration) -> &mut Self {
+        self.clock.advance(by);
+        self.app.tick().expect("tick failed");
+        self
+    }
+
+    pub fn render(&self, width: u16, height: u16) -> String {
+        let buffer = render_to_buffer(&self.app, width, height).expect("render failed");
+        let mut output = String::new();
+        for y in 0..buffer.area.height {
+            let row: String = (0..buffer.area.width)
+                .map(|x| buffer.get(x, y).symbol())
+                .collect();
+            output.push_str(row.trim_end());
+            output.push('\n');
+        }
+        output
+    }
+
+    pub fn assert_snapshot(&self, name: &str) {
+        self.assert_snapshot_sized(name, WIDTH, HEIGHT);
+    }
+
+    pub fn assert_snapshot_sized(&self, name: &str, width: u16, height: u16) {
+        assert_snapshot(name, &self.render(width, height));
+    }
+}
+
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{name}.snap"));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot {}, run with UPDATE_SNAPSHOTS=1 to create it\n\n{actual}",
+            path.display()
+        )
+    });
+
+    if expected != actual {
+        let diff: String = expected
+            .lines()
+            .zip(actual.lines())
+            .enumerate()
+            .filter(|(_, (e, a))| e != a)
+            .map(|(i, (e, a))| format!("line {:>2}\n  - {e}\n  + {a}\n", i + 1))
+            .collect();
+
+        panic!(
+            "snapshot {name} does not match, run with UPDATE_SNAPSHOTS=1 to accept\n\n{diff}\nactual:\n{actual}"
+        );
+    }
+}
@@ -0,0 +1,216 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::Harness;
+use crossterm::event::KeyCode;
+use std::path::PathBuf;
+use tuitype::config::{Config, KeyChord, LOCAL_CONFIG_FILE, LocalConfig, TestMode};
+use tuitype::storage::{MemoryStorage, Storage};
+use tuitype::ui::ThemeType;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tuitype-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn first_run_writes_a_documented_toml_config() {
+    let storage = MemoryStorage::new();
+    let loaded = Config::load_from(&storage);
+    assert_eq!(loaded.config, Config::default());
+
+    let written = storage.get(Config::STORAGE_KEY).unwrap();
+    assert!(written.starts_with("# TuiType settings."));
+    assert!(written.contains(
+        "\n# \"Light\", \"Dark\", \"Sepia\", \"Matrix\" or \"Ocean\".\ntheme_type = \"Dark\"\n"
+    ));
+    assert!(written.contains("\n# seed = 20240501\n"));
+    assert!(written.contains("\n[keybindings]\ncommand_palette = \"ctrl+p\"\n"));
+    assert!(storage.get(Config::JSON_STORAGE_KEY).is_none());
+
+    let reloaded = Config::load_from(&storage);
+    assert!(reloaded.warnings.is_empty(), "{:?}", reloaded.warnings);
+    assert_eq!(reloaded.config, Config::default());
+}
+
+#[test]
+fn toml_settings_round_trip_and_keep_their_documentation() {
+    let storage = MemoryStorage::new();
+    let config = Config {
+        test_mode: TestMode::Timed(30),
+        theme_type: ThemeType::Matrix,
+        custom_text: Some("say \"hi\"".to_string()),
+        word_list: Some(vec!["borrow".to_string(), "trait".to_string()]),
+        seed: Some(7),
+        ..Config::default()
+    };
+    config.save_to(&storage).unwrap();
+
+    let written = storage.get(Config::STORAGE_KEY).unwrap();
+    assert!(written.contains("test_mode = { Timed = 30 }"));
+    assert!(
+        written.contains("# Generate the same text as anyone else using this seed.\nseed = 7\n")
+    );
+
+    let loaded = Config::load_from(&storage);
+    assert!(loaded.warnings.is_empty(), "{:?}", loaded.warnings);
+    assert_eq!(loaded.config, config);
+}
+
+#[test]
+fn hand_written_toml_is_tolerated_like_json() {
+    let storage = MemoryStorage::new();
+    storage
+        .write(
+            Config::STORAGE_KEY,
+            "# my settings\ntheme_type = \"Ocean\"\ncaret_style = \"Wavy\"\n\n[keybindings]\nquit = \"ctrl+q\"\n",
+        )
+        .unwrap();
+
+    let loaded = Config::load_from(&storage);
+    assert_eq!(loaded.config.theme_type, ThemeType::Ocean);
+    assert_eq!(loaded.config.caret_style, Config::default().caret_style);
+    assert_eq!(
+        loaded.config.keybindings.quit,
+        "ctrl+q".parse::<KeyChord>().unwrap()
+    );
+    assert!(loaded.warnings[0].contains("caret_style"));
+}
+
+#[test]
+fn toml_syntax_errors_name_the_line_and_back_up_the_file() {
+    let storage = MemoryStorage::new();
+    let contents = "theme_type = \"Sepia\"\ndifficulty = \n";
+    storage.write(Config::STORAGE_KEY, contents).unwrap();
+
+    let loaded = Config::load_from(&storage);
+    assert_eq!(loaded.config, Config::default());
+    assert!(loaded.warnings[0].contains("config.toml could not be parsed (line 2"));
+    assert!(loaded.warnings[1].starts_with("The original file was saved as config.toml."));
+}
+
+#[test]
+fn existing_json_configs_stay_json() {
+    let storage = MemoryStorage::new();
+    storage
+        .write(Config::JSON_STORAGE_KEY, r#"{"theme_type": "Sepia"}"#)
+        .unwrap();
+
+    let mut config = Config::load_from(&storage).config;
+    assert_eq!(config.theme_type, ThemeType::Sepia);
+
+    config.theme_type = ThemeType::Light;
+    config.save_to(&storage).unwrap();
+    assert!(storage.get(Config::STORAGE_KEY).is_none());
+    assert_eq!(
+        Config::load_from(&storage).config.theme_type,
+        ThemeType::Light
+    );
+}
+
+#[test]
+fn word_list_replaces_the_difficulty_words() {
+    let config = Config {
+        test_mode: TestMode::Words(4),
+        word_list: Some(vec!["ferris".to_string()]),
+        ..Config::default()
+    };
+    let harness = Harness::new(config);
+    assert_eq!(
+        harness.app.session.text_source.full_text(),
+        "ferris ferris ferris ferris"
+    );
+}
+
+#[test]
+fn local_config_is_found_in_a_parent_directory() {
+    let root = temp_dir("local-find");
+    let nested = root.join("src").join("bin");
+    std::fs::create_dir_all(&nested).unwrap();
+    assert_eq!(LocalConfig::find(&nested), None);
+
+    std::fs::write(root.join(LOCAL_CONFIG_FILE), "").unwrap();
+    assert_eq!(
+        LocalConfig::find(&nested),
+        Some(root.join(LOCAL_CONFIG_FILE))
+    );
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn local_config_overrides_settings_without_saving_them() {
+    let root = temp_dir("local-apply");
+    std::fs::create_dir_all(root.join("drills")).unwrap();
+    std::fs::write(
+        root.join("drills").join("intro.txt"),
+        "cargo  build\ncargo test\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join(LOCAL_CONFIG_FILE),
+        "test_mode = \"Custom\"\ncustom_text_file = \"drills/intro.txt\"\ntheme = \"Sepia\"\n",
+    )
+    .unwrap();
+    let local = LocalConfig::load(root.join(LOCAL_CONFIG_FILE)).unwrap();
+
+    let mut harness = Harness::new(Config::default());
+    let warnings = harness.app.apply_local_config(local);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains(": theme."), "{}", warnings[0]);
+    assert_eq!(harness.app.config.test_mode, TestMode::Custom);
+    assert_eq!(
+        harness.app.session.text_source.full_text(),
+        "cargo build cargo test"
+    );
+
+    harness.press(KeyCode::Esc).type_text("3").type_text("3");
+    assert_eq!(harness.app.config.theme_type, ThemeType::Sepia);
+    let saved = Config::load_from(&*harness.storage).config;
+    assert_eq!(saved.theme_type, ThemeType::Sepia);
+    assert_eq!(saved.test_mode, Config::default().test_mode);
+    assert_eq!(saved.custom_text, None);
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn local_config_reports_missing_files() {
+    let root = temp_dir("local-missing");
+    std::fs::write(
+        root.join(LOCAL_CONFIG_FILE),
+        "word_list_file = \"words.txt\"\n",
+    )
+    .unwrap();
+
+    let err = LocalConfig::load(root.join(LOCAL_CONFIG_FILE)).unwrap_err();
+    assert!(format!("{err:#}").contains("could not read word_list_file"));
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn local_config_only_reads_files_inside_its_directory() {
+    let outside = temp_dir("local-outside");
+    std::fs::write(outside.join("secret.txt"), "hunter2").unwrap();
+    let root = outside.join("project");
+    std::fs::create_dir_all(&root).unwrap();
+
+    for file in [
+        "../secret.txt".to_string(),
+        outside.join("secret.txt").display().to_string(),
+    ] {
+        std::fs::write(
+            root.join(LOCAL_CONFIG_FILE),
+            format!("word_list_file = {file:?}\n"),
+        )
+        .unwrap();
+
+        let err = LocalConfig::load(root.join(LOCAL_CONFIG_FILE)).unwrap_err();
+        assert!(format!("{err:#}").contains("is outside"), "{err:#}");
+    }
+
+    std::fs::remove_dir_all(outside).unwrap();
+}
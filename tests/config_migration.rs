@@ -0,0 +1,199 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::Harness;
+use crossterm::event::KeyCode;
+use serde_json::{Map, Value};
+use tuitype::config::{CONFIG_VERSION, Config, KeyDebounce, Migration, TestMode, migrate_with};
+use tuitype::storage::{MemoryStorage, Storage};
+use tuitype::ui::{ThemeType, WarningState};
+
+fn stored(contents: &str) -> MemoryStorage {
+    let storage = MemoryStorage::new();
+    storage.write(Config::JSON_STORAGE_KEY, contents).unwrap();
+    storage
+}
+
+fn backed_up(storage: &MemoryStorage, warnings: &[String], contents: &str) -> bool {
+    warnings
+        .iter()
+        .filter_map(|warning| warning.strip_prefix("The original file was saved as "))
+        .filter_map(|key| storage.get(key.trim_end_matches('.')))
+        .any(|backup| backup == contents)
+}
+
+#[test]
+fn unversioned_config_without_newer_fields_loads() {
+    let storage = stored(r#"{"test_mode": {"Words": 25}, "theme_type": "Sepia"}"#);
+
+    let loaded = Config::load_from(&storage);
+    assert!(loaded.warnings.is_empty(), "{:?}", loaded.warnings);
+    assert_eq!(loaded.config.version, CONFIG_VERSION);
+    assert_eq!(loaded.config.test_mode, TestMode::Words(25));
+    assert_eq!(loaded.config.theme_type, ThemeType::Sepia);
+    assert_eq!(loaded.config.key_debounce, KeyDebounce::default());
+    assert_eq!(loaded.config.keybindings, Config::default().keybindings);
+}
+
+#[test]
+fn invalid_settings_are_dropped_one_at_a_time() {
+    let contents = r#"{
+        "version": 1,
+        "theme_type": "Neon",
+        "difficulty": "Hard",
+        "debounce_ms": -5,
+        "keybindings": {"restart": "hyper+q", "quit": "ctrl+q"}
+    }"#;
+    let storage = stored(contents);
+
+    let loaded = Config::load_from(&storage);
+    assert_eq!(loaded.config.theme_type, ThemeType::Dark);
+    assert_eq!(loaded.config.difficulty, tuitype::config::Difficulty::Hard);
+    assert_eq!(loaded.config.debounce_ms, Config::default().debounce_ms);
+    assert_eq!(
+        loaded.config.keybindings.restart,
+        Config::default().keybindings.restart
+    );
+    assert_ne!(
+        loaded.config.keybindings.quit,
+        Config::default().keybindings.quit
+    );
+
+    let rejected = &loaded.warnings[0];
+    for setting in ["theme_type", "debounce_ms", "keybindings.restart"] {
+        assert!(rejected.contains(setting), "{rejected}");
+    }
+    assert!(!rejected.contains("difficulty"), "{rejected}");
+    assert!(backed_up(&storage, &loaded.warnings, contents));
+}
+
+#[test]
+fn unparsable_config_is_backed_up_and_reset() {
+    let contents = r#"{"theme_type": "Sepia","#;
+    let storage = stored(contents);
+
+    let loaded = Config::load_from(&storage);
+    assert_eq!(loaded.config, Config::default());
+    assert!(loaded.warnings[0].contains("could not be parsed"));
+    assert!(backed_up(&storage, &loaded.warnings, contents));
+}
+
+#[test]
+fn config_from_a_newer_version_warns() {
+    let contents = format!(
+        r#"{{"version": {}, "theme_type": "Ocean", "shiny_new_setting": true}}"#,
+        CONFIG_VERSION + 1
+    );
+    let storage = stored(&contents);
+
+    let loaded = Config::load_from(&storage);
+    assert_eq!(loaded.config.theme_type, ThemeType::Ocean);
+    assert_eq!(loaded.config.version, CONFIG_VERSION);
+    assert!(loaded.warnings[0].contains("newer version"));
+    assert!(backed_up(&storage, &loaded.warnings, &contents));
+}
+
+#[test]
+fn repaired_config_is_saved_so_later_loads_are_clean() {
+    let contents = r#"{"version": 1, "theme_type": "Neon", "difficulty": "Hard"}"#;
+    let storage = stored(contents);
+
+    let loaded = Config::load_from(&storage);
+    assert!(backed_up(&storage, &loaded.warnings, contents));
+
+    let reloaded = Config::load_from(&storage);
+    assert!(reloaded.warnings.is_empty(), "{:?}", reloaded.warnings);
+    assert_eq!(reloaded.config, loaded.config);
+}
+
+#[test]
+fn newer_config_is_left_alone_and_backed_up_once() {
+    let contents = format!(
+        r#"{{"version": {}, "theme_type": "Ocean"}}"#,
+        CONFIG_VERSION + 1
+    );
+    let storage = stored(&contents);
+
+    let first = Config::load_from(&storage);
+    let second = Config::load_from(&storage);
+    assert_eq!(first.warnings, second.warnings);
+    assert!(backed_up(&storage, &second.warnings, &contents));
+    assert_eq!(storage.get(Config::JSON_STORAGE_KEY), Some(contents));
+}
+
+fn theme_to_theme_type(config: &mut Map<String, Value>) {
+    if let Some(theme) = config.remove("theme") {
+        config.insert("theme_type".to_string(), theme);
+    }
+}
+
+fn words_count_to_test_mode(config: &mut Map<String, Value>) {
+    if let Some(count) = config.remove("words") {
+        config.insert(
+            "test_mode".to_string(),
+            serde_json::json!({ "Words": count }),
+        );
+    }
+}
+
+#[test]
+fn unversioned_config_with_renamed_and_retyped_fields_migrates() {
+    let migrations: [Migration; 2] = [theme_to_theme_type, words_count_to_test_mode];
+    let Value::Object(mut stored) = serde_json::json!({"theme": "Matrix", "words": 50}) else {
+        unreachable!()
+    };
+
+    migrate_with(&mut stored, &migrations);
+    assert_eq!(stored["version"], 2);
+    let (config, rejected) = Config::default().with_overrides(stored);
+    assert!(rejected.is_empty(), "{rejected:?}");
+    assert_eq!(config.theme_type, ThemeType::Matrix);
+    assert_eq!(config.test_mode, TestMode::Words(50));
+
+    let Value::Object(mut stored) =
+        serde_json::json!({"version": 1, "theme_type": "Ocean", "theme": "Matrix", "words": 50})
+    else {
+        unreachable!()
+    };
+    migrate_with(&mut stored, &migrations);
+    assert_eq!(stored["theme"], "Matrix");
+    let (config, _) = Config::default().with_overrides(stored);
+    assert_eq!(config.theme_type, ThemeType::Ocean);
+    assert_eq!(config.test_mode, TestMode::Words(50));
+}
+
+#[test]
+fn current_config_loads_without_warnings_or_backups() {
+    let storage = MemoryStorage::new();
+    Config::default().save_to(&storage).unwrap();
+
+    let loaded = Config::load_from(&storage);
+    assert!(loaded.warnings.is_empty());
+    assert_eq!(loaded.config, Config::default());
+}
+
+#[test]
+fn recovery_warning_is_shown_until_dismissed() {
+    let storage = stored("not json");
+    let loaded = Config::load_from(&storage);
+
+    let mut harness = Harness::custom("hi");
+    harness.app.show_config_warnings(loaded.warnings);
+    assert!(harness.render(100, 30).contains("SETTINGS RECOVERED"));
+
+    harness.type_text("h");
+    assert_eq!(harness.app.session.typed_text(), "");
+
+    harness.press(KeyCode::Enter);
+    assert_eq!(harness.app.warning_state, WarningState::None);
+    harness.type_text("h");
+    assert_eq!(harness.app.session.typed_text(), "h");
+}
+
+#[test]
+fn no_warning_is_shown_for_a_clean_load() {
+    let mut harness = Harness::custom("hi");
+    harness.app.show_config_warnings(Vec::new());
+    assert_eq!(harness.app.warning_state, WarningState::None);
+}
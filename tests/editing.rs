@@ -0,0 +1,107 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::Harness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tuitype::config::{BackspaceMode, Config, TestMode};
+use tuitype::replay::Replay;
+use tuitype::stats::TestResult;
+
+fn with_mode(text: &str, mode: BackspaceMode) -> Harness {
+    Harness::new(Config {
+        test_mode: TestMode::Custom,
+        custom_text: Some(text.to_string()),
+        backspace_mode: mode,
+        ..Config::default()
+    })
+}
+
+#[test]
+fn word_deletion_shortcuts_remove_the_previous_word() {
+    for (code, modifiers) in [
+        (KeyCode::Backspace, KeyModifiers::CONTROL),
+        (KeyCode::Backspace, KeyModifiers::ALT),
+        (KeyCode::Char('w'), KeyModifiers::CONTROL),
+    ] {
+        let mut harness = Harness::custom("alpha beta gamma");
+        harness.type_text("alpha bexa ").press_with(code, modifiers);
+
+        let session = &harness.app.session;
+        assert_eq!(session.typed_text(), "alpha ", "{code:?} {modifiers:?}");
+        assert_eq!(session.cursor_pos, 6);
+        assert_eq!(session.stats.corrections, 5);
+        assert_eq!(session.stats.incorrect_chars, 0);
+    }
+}
+
+#[test]
+fn ctrl_h_is_a_plain_backspace() {
+    let mut harness = Harness::custom("alpha beta gamma");
+    harness
+        .type_text("alpha bexa")
+        .press_with(KeyCode::Char('h'), KeyModifiers::CONTROL);
+
+    let session = &harness.app.session;
+    assert_eq!(session.typed_text(), "alpha bex");
+    assert_eq!(session.stats.corrections, 1);
+}
+
+#[test]
+fn confidence_mode_keeps_correct_words_locked() {
+    let mut harness = with_mode("alpha beta gamma", BackspaceMode::Confidence);
+    harness.type_text("alpha ").press(KeyCode::Backspace);
+    assert_eq!(harness.app.session.typed_text(), "alpha ");
+
+    harness
+        .press_with(KeyCode::Char('w'), KeyModifiers::CONTROL)
+        .type_text("bexa ")
+        .press(KeyCode::Backspace);
+    assert_eq!(harness.app.session.typed_text(), "alpha bexa");
+
+    harness.press_with(KeyCode::Backspace, KeyModifiers::CONTROL);
+    assert_eq!(harness.app.session.typed_text(), "alpha ");
+    assert_eq!(harness.app.session.stats.corrections, 5);
+}
+
+#[test]
+fn disabled_mode_ignores_all_deletions() {
+    let mut harness = with_mode("alpha beta", BackspaceMode::Disabled);
+    harness
+        .type_text("alpx")
+        .press(KeyCode::Backspace)
+        .press_with(KeyCode::Char('w'), KeyModifiers::CONTROL);
+
+    let session = &harness.app.session;
+    assert_eq!(session.typed_text(), "alpx");
+    assert_eq!(session.stats.corrections, 0);
+}
+
+#[test]
+fn replay_applies_word_deletions() {
+    let mut harness = Harness::custom("alpha beta gamma");
+    harness
+        .type_text("alpha bexa")
+        .press_with(KeyCode::Char('w'), KeyModifiers::CONTROL)
+        .type_text("beta gamma");
+
+    let session = &harness.app.session;
+    assert!(session.test_complete);
+    let result = TestResult {
+        timestamp: chrono::Utc::now(),
+        duration: session.duration_seconds(),
+        wpm: session.stats.wpm,
+        accuracy: session.stats.accuracy,
+        mode: "custom".to_string(),
+        text: session.text_source.full_text().to_string(),
+        keystrokes: session.keystrokes.clone(),
+    };
+    let mut replay = Replay::new(&result);
+    while !replay.is_finished() {
+        replay.step_forward();
+        if replay.applied_keystrokes() == 11 {
+            assert_eq!(replay.typed.iter().collect::<String>(), "alpha ");
+        }
+    }
+    assert_eq!(replay.typed.iter().collect::<String>(), "alpha beta gamma");
+}
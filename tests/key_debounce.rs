@@ -0,0 +1,149 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::Harness;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use std::time::Duration;
+use tuitype::config::{Config, KeyDebounce};
+use tuitype::input::{Debouncer, Event, KeyDecision};
+
+const WINDOW: Duration = Duration::from_millis(60);
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+fn fixed_window_drops_fast_double_letters() {
+    let mut debouncer = Debouncer::new(KeyDebounce::FixedWindow, WINDOW);
+    let l = KeyCode::Char('l');
+
+    assert_eq!(
+        debouncer.check(l, KeyEventKind::Press, ms(0)),
+        KeyDecision::Accept
+    );
+    assert_eq!(
+        debouncer.check(l, KeyEventKind::Release, ms(10)),
+        KeyDecision::Ignore
+    );
+    assert_eq!(
+        debouncer.check(l, KeyEventKind::Press, ms(40)),
+        KeyDecision::Drop
+    );
+    assert_eq!(
+        debouncer.check(l, KeyEventKind::Press, ms(70)),
+        KeyDecision::Accept
+    );
+    assert_eq!(
+        debouncer.check(KeyCode::Char('e'), KeyEventKind::Press, ms(75)),
+        KeyDecision::Accept
+    );
+    assert_eq!(debouncer.dropped(), 1);
+}
+
+#[test]
+fn press_release_keeps_fast_double_letters() {
+    let mut debouncer = Debouncer::new(KeyDebounce::PressRelease, WINDOW);
+    let l = KeyCode::Char('l');
+
+    assert_eq!(
+        debouncer.check(l, KeyEventKind::Press, ms(0)),
+        KeyDecision::Accept
+    );
+    assert_eq!(
+        debouncer.check(l, KeyEventKind::Release, ms(10)),
+        KeyDecision::Ignore
+    );
+    assert_eq!(
+        debouncer.check(l, KeyEventKind::Press, ms(20)),
+        KeyDecision::Accept
+    );
+    assert_eq!(
+        debouncer.check(l, KeyEventKind::Repeat, ms(40)),
+        KeyDecision::Accept
+    );
+    assert_eq!(debouncer.dropped(), 0);
+}
+
+#[test]
+fn held_keys_repeat_without_counting_drops() {
+    for policy in [KeyDebounce::FixedWindow, KeyDebounce::PressRelease] {
+        let mut debouncer = Debouncer::new(policy, WINDOW);
+        let backspace = KeyCode::Backspace;
+
+        assert_eq!(
+            debouncer.check(backspace, KeyEventKind::Press, ms(0)),
+            KeyDecision::Accept
+        );
+        for at in [30, 60, 90, 120] {
+            assert_eq!(
+                debouncer.check(backspace, KeyEventKind::Repeat, ms(at)),
+                KeyDecision::Accept
+            );
+        }
+        assert_eq!(
+            debouncer.check(backspace, KeyEventKind::Release, ms(130)),
+            KeyDecision::Ignore
+        );
+        assert_eq!(
+            debouncer.check(backspace, KeyEventKind::Press, ms(140)),
+            KeyDecision::Accept
+        );
+        assert_eq!(debouncer.dropped(), 0);
+    }
+}
+
+#[test]
+fn press_release_falls_back_to_window_without_release_events() {
+    let mut debouncer = Debouncer::new(KeyDebounce::PressRelease, WINDOW);
+    let s = KeyCode::Char('s');
+
+    assert_eq!(
+        debouncer.check(s, KeyEventKind::Press, ms(0)),
+        KeyDecision::Accept
+    );
+    assert_eq!(
+        debouncer.check(s, KeyEventKind::Press, ms(30)),
+        KeyDecision::Drop
+    );
+    assert_eq!(
+        debouncer.check(s, KeyEventKind::Press, ms(90)),
+        KeyDecision::Accept
+    );
+    assert_eq!(debouncer.dropped(), 1);
+}
+
+#[test]
+fn off_accepts_everything() {
+    let mut debouncer = Debouncer::new(KeyDebounce::Off, WINDOW);
+    let e = KeyCode::Char('e');
+
+    assert_eq!(
+        debouncer.check(e, KeyEventKind::Press, ms(0)),
+        KeyDecision::Accept
+    );
+    assert_eq!(
+        debouncer.check(e, KeyEventKind::Press, ms(1)),
+        KeyDecision::Accept
+    );
+    assert_eq!(
+        debouncer.check(e, KeyEventKind::Repeat, ms(2)),
+        KeyDecision::Accept
+    );
+    assert_eq!(debouncer.dropped(), 0);
+}
+
+#[test]
+fn dropped_keys_are_counted_per_test() {
+    let mut harness = Harness::new(Config::default());
+    let key = KeyEvent::from(KeyCode::Char('l'));
+
+    harness.app.handle_events(Event::KeyDropped(key)).unwrap();
+    harness.app.handle_events(Event::KeyDropped(key)).unwrap();
+    assert_eq!(harness.app.session.dropped_keys, 2);
+    assert_eq!(harness.app.session.snapshot().dropped_keys, 2);
+
+    harness.press(KeyCode::Tab);
+    assert_eq!(harness.app.session.dropped_keys, 0);
+}
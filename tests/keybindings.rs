@@ -0,0 +1,179 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::Harness;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tuitype::config::{Config, KeyChord, Keybindings, TestMode};
+use tuitype::ui::MenuState;
+
+fn with_bindings(keybindings: Keybindings) -> Harness {
+    Harness::new(Config {
+        test_mode: TestMode::Custom,
+        custom_text: Some("alpha beta".to_string()),
+        keybindings,
+        ..Config::default()
+    })
+}
+
+#[test]
+fn chords_parse_and_round_trip_through_config() {
+    let chord: KeyChord = "Ctrl+Shift+R".parse().unwrap();
+    assert_eq!(chord.to_string(), "Ctrl+Shift+R");
+    assert!(chord.matches(&KeyEvent::new(
+        KeyCode::Char('R'),
+        KeyModifiers::CONTROL | KeyModifiers::SHIFT
+    )));
+
+    assert_eq!("f5".parse::<KeyChord>().unwrap().to_string(), "F5");
+    assert_eq!("ctrl++".parse::<KeyChord>().unwrap().to_string(), "Ctrl++");
+    assert!("hyper+x".parse::<KeyChord>().is_err());
+    assert!("ctrl+".parse::<KeyChord>().is_err());
+
+    let config: Config = serde_json::from_str(
+        &serde_json::to_string(&Config {
+            keybindings: Keybindings {
+                restart: "alt+enter".parse().unwrap(),
+                ..Keybindings::default()
+            },
+            ..Config::default()
+        })
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(config.keybindings.restart.to_string(), "Alt+Enter");
+    assert_eq!(config.keybindings.quit, Keybindings::default().quit);
+}
+
+#[test]
+fn partial_keybindings_fall_back_to_defaults() {
+    let mut value = serde_json::to_value(Config::default()).unwrap();
+    value["keybindings"] = serde_json::json!({ "restart": "ctrl+r" });
+
+    let config: Config = serde_json::from_value(value).unwrap();
+    assert_eq!(config.keybindings.restart, KeyChord::ctrl('r'));
+    assert_eq!(
+        config.keybindings.open_menu,
+        Keybindings::default().open_menu
+    );
+}
+
+#[test]
+fn conflicting_and_typing_bindings_are_rejected() {
+    let stored = serde_json::json!({
+        "keybindings": {
+            "restart": "a",
+            "quit": "tab",
+            "toggle_stats": "backspace",
+            "next_test": "ctrl+p",
+            "repeat_text": "x",
+            "open_menu": "ctrl+o",
+        }
+    });
+    let serde_json::Value::Object(stored) = stored else {
+        unreachable!();
+    };
+
+    let (config, mut rejected) = Config::default().with_overrides(stored);
+    rejected.sort();
+    assert_eq!(
+        rejected,
+        [
+            "keybindings.next_test",
+            "keybindings.quit",
+            "keybindings.restart",
+            "keybindings.toggle_stats",
+        ]
+    );
+
+    let defaults = Keybindings::default();
+    assert_eq!(config.keybindings.restart, defaults.restart);
+    assert_eq!(config.keybindings.quit, defaults.quit);
+    assert_eq!(config.keybindings.next_test, defaults.next_test);
+    assert_eq!(
+        config.keybindings.repeat_text,
+        KeyChord::key(KeyCode::Char('x'))
+    );
+    assert_eq!(config.keybindings.open_menu, KeyChord::ctrl('o'));
+}
+
+#[test]
+fn remapped_restart_frees_tab() {
+    let mut harness = with_bindings(Keybindings {
+        restart: KeyChord::ctrl('r'),
+        ..Keybindings::default()
+    });
+
+    harness.type_text("alp").press(KeyCode::Tab);
+    assert_eq!(harness.app.session.typed_text(), "alp");
+
+    harness.press_with(KeyCode::Char('r'), KeyModifiers::CONTROL);
+    assert_eq!(harness.app.session.typed_text(), "");
+}
+
+#[test]
+fn remapped_menu_quit_and_stats_keys() {
+    let mut harness = with_bindings(Keybindings {
+        open_menu: "ctrl+o".parse().unwrap(),
+        quit: "ctrl+q".parse().unwrap(),
+        toggle_stats: KeyChord::key(KeyCode::F(2)),
+        ..Keybindings::default()
+    });
+
+    harness.press(KeyCode::Esc);
+    assert_eq!(harness.app.menu_state, MenuState::Typing);
+    harness.press_with(KeyCode::Char('o'), KeyModifiers::CONTROL);
+    assert_eq!(harness.app.menu_state, MenuState::MainMenu(0));
+    harness.press(KeyCode::Esc);
+
+    harness.press(KeyCode::F(2));
+    assert!(!harness.app.show_stats);
+    assert!(!harness.render(100, 30).contains("Accuracy"));
+    harness.press(KeyCode::F(2));
+    assert!(harness.app.show_stats);
+
+    harness.press_with(KeyCode::Char('c'), KeyModifiers::CONTROL);
+    assert!(harness.app.running);
+    harness.press_with(KeyCode::Char('q'), KeyModifiers::CONTROL);
+    assert!(!harness.app.running);
+}
+
+#[test]
+fn results_screen_uses_bound_keys() {
+    let mut harness = Harness::new(Config {
+        test_mode: TestMode::Words(2),
+        keybindings: Keybindings {
+            repeat_text: KeyChord::key(KeyCode::Char('a')),
+            next_test: KeyChord::key(KeyCode::Char(' ')),
+            ..Keybindings::default()
+        },
+        ..Config::default()
+    });
+
+    let text = harness.app.session.text_source.full_text().to_string();
+    harness.type_text(&text);
+    assert_eq!(harness.app.menu_state, MenuState::TestComplete);
+    let screen = harness.render(100, 30);
+    assert!(screen.contains("Press A to retry this seed"), "{screen}");
+    assert!(screen.contains("Press SPACE to restart"), "{screen}");
+
+    harness.press(KeyCode::Enter);
+    assert_eq!(harness.app.menu_state, MenuState::TestComplete);
+    harness.press(KeyCode::Char('a'));
+    assert_eq!(harness.app.menu_state, MenuState::Typing);
+    assert_eq!(harness.app.session.text_source.full_text(), text);
+}
+
+#[test]
+fn help_lists_current_bindings() {
+    let mut harness = with_bindings(Keybindings {
+        restart: "ctrl+r".parse().unwrap(),
+        ..Keybindings::default()
+    });
+
+    harness.press(KeyCode::Esc).type_text("6");
+    assert_eq!(harness.app.menu_state, MenuState::Help);
+    let screen = harness.render(100, 30);
+    assert!(screen.contains("Ctrl+R: Quick restart test"), "{screen}");
+    assert!(!screen.contains("Tab: Quick restart test"), "{screen}");
+}
@@ -0,0 +1,146 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::{HEIGHT, Harness, WIDTH};
+use crossterm::event::{KeyCode, MouseEventKind};
+use tuitype::config::{Config, TestMode};
+use tuitype::stats::{TestHistory, TestResult};
+use tuitype::ui::MenuState;
+
+fn locate(harness: &Harness, needle: &str) -> (u16, u16) {
+    harness
+        .render(WIDTH, HEIGHT)
+        .lines()
+        .enumerate()
+        .find_map(|(row, line)| {
+            line.find(needle)
+                .map(|byte| (line[..byte].chars().count() as u16, row as u16))
+        })
+        .unwrap_or_else(|| panic!("'{needle}' is not on screen"))
+}
+
+fn history(count: usize) -> TestHistory {
+    let mut history = TestHistory::default();
+    for i in 0..count {
+        history.add_result(TestResult {
+            timestamp: chrono::Utc::now(),
+            duration: 10.0,
+            wpm: 40.0 + i as f64,
+            accuracy: 100.0,
+            mode: "10 words".to_string(),
+            text: String::new(),
+            keystrokes: Vec::new(),
+        });
+    }
+    history
+}
+
+#[test]
+fn vim_keys_move_select_and_go_back() {
+    let mut harness = Harness::custom("alpha beta");
+    harness.press(KeyCode::Esc).type_text("jj");
+    assert_eq!(harness.app.menu_state, MenuState::MainMenu(2));
+
+    harness.type_text("G");
+    assert_eq!(harness.app.menu_state, MenuState::MainMenu(7));
+    harness.type_text("gk");
+    assert_eq!(harness.app.menu_state, MenuState::MainMenu(0));
+
+    harness.type_text("l");
+    assert_eq!(harness.app.menu_state, MenuState::TestModeMenu(0));
+    harness.type_text("G");
+    assert_eq!(harness.app.menu_state, MenuState::TestModeMenu(3));
+
+    harness.type_text("h");
+    assert_eq!(harness.app.menu_state, MenuState::Typing);
+    assert_eq!(harness.app.session.typed_text(), "");
+}
+
+#[test]
+fn vim_keys_scroll_help() {
+    let mut harness = Harness::custom("alpha beta");
+    harness.press(KeyCode::Esc).type_text("6");
+    assert_eq!(harness.app.menu_state, MenuState::Help);
+
+    harness.type_text("jj");
+    assert_eq!(harness.app.help_scroll_offset, 2);
+
+    harness.render(WIDTH, HEIGHT);
+    harness.type_text("G");
+    let bottom = harness.app.help_scroll_offset;
+    assert!(bottom > 2);
+    assert!(
+        harness
+            .render(WIDTH, HEIGHT)
+            .contains("Press ESC to return")
+    );
+
+    harness.type_text("g");
+    assert_eq!(harness.app.help_scroll_offset, 0);
+}
+
+#[test]
+fn clicking_a_menu_item_selects_it() {
+    let mut harness = Harness::custom("alpha beta");
+    harness.press(KeyCode::Esc);
+
+    let (column, row) = locate(&harness, "3. Theme");
+    harness.click(column, row);
+    assert_eq!(harness.app.menu_state, MenuState::ThemeMenu(0));
+
+    let (column, row) = locate(&harness, "6. Back");
+    harness.click(column + 2, row);
+    assert_eq!(harness.app.menu_state, MenuState::MainMenu(0));
+
+    harness.click(0, 0);
+    assert_eq!(harness.app.menu_state, MenuState::MainMenu(0));
+}
+
+#[test]
+fn scroll_wheel_moves_through_help_and_history() {
+    let mut harness = Harness::custom("alpha beta");
+    harness.app.set_history(history(3));
+
+    harness.press(KeyCode::Esc).type_text("6");
+    harness
+        .mouse(MouseEventKind::ScrollDown, 10, 10)
+        .mouse(MouseEventKind::ScrollDown, 10, 10);
+    assert_eq!(harness.app.help_scroll_offset, 2);
+    harness.mouse(MouseEventKind::ScrollUp, 10, 10);
+    assert_eq!(harness.app.help_scroll_offset, 1);
+
+    harness.press(KeyCode::Esc).type_text("5");
+    assert_eq!(harness.app.menu_state, MenuState::HistoryMenu(0));
+    harness
+        .mouse(MouseEventKind::ScrollDown, 10, 10)
+        .mouse(MouseEventKind::ScrollDown, 10, 10)
+        .mouse(MouseEventKind::ScrollDown, 10, 10);
+    assert_eq!(harness.app.menu_state, MenuState::HistoryMenu(2));
+
+    let (column, row) = locate(&harness, "41.0 WPM");
+    harness.click(column, row);
+    assert_eq!(harness.app.menu_state, MenuState::HistoryMenu(1));
+}
+
+#[test]
+fn results_screen_notes_are_clickable() {
+    let mut harness = Harness::new(Config {
+        test_mode: TestMode::Words(2),
+        ..Config::default()
+    });
+    let text = harness.app.session.text_source.full_text().to_string();
+
+    harness.type_text(&text);
+    assert_eq!(harness.app.menu_state, MenuState::TestComplete);
+    let (column, row) = locate(&harness, "retry this seed");
+    harness.click(column, row);
+    assert_eq!(harness.app.menu_state, MenuState::Typing);
+    assert_eq!(harness.app.session.text_source.full_text(), text);
+
+    harness.type_text(&text);
+    let (column, row) = locate(&harness, "to restart typing");
+    harness.click(column, row);
+    assert_eq!(harness.app.menu_state, MenuState::Typing);
+    assert!(harness.app.session.typed_chars.is_empty());
+}
@@ -0,0 +1,48 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::{HEIGHT, Harness, WIDTH};
+use tuitype::config::{Config, PaceCaret, TestMode};
+use tuitype::pace::Ghost;
+use tuitype::stats::{TestHistory, TestResult};
+
+fn result(mode: &str, wpm: f64) -> TestResult {
+    TestResult {
+        timestamp: chrono::Utc::now(),
+        duration: 15.0,
+        wpm,
+        accuracy: 100.0,
+        mode: mode.to_string(),
+        text: String::new(),
+        keystrokes: Vec::new(),
+    }
+}
+
+#[test]
+fn personal_best_pace_only_uses_the_same_mode() {
+    let config = Config {
+        test_mode: TestMode::Words(100),
+        pace_caret: PaceCaret::PersonalBest,
+        ..Config::default()
+    };
+    let mut history = TestHistory::default();
+    history.add_result(result("15 seconds", 140.0));
+
+    assert_eq!(history.personal_best("100 words"), None);
+    assert_eq!(Ghost::from_config(&config, Some(&history)), None);
+
+    let mut harness = Harness::new(config.clone());
+    harness.app.set_history(history.clone());
+    assert!(harness.render(WIDTH, HEIGHT).contains("Pace: no PB yet"));
+
+    history.add_result(result("100 words", 60.0));
+    assert_eq!(
+        Ghost::from_config(&config, Some(&history)),
+        Some(Ghost::Steady {
+            chars_per_second: 5.0
+        })
+    );
+    harness.app.set_history(history);
+    assert!(!harness.render(WIDTH, HEIGHT).contains("no PB yet"));
+}
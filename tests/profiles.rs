@@ -0,0 +1,160 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::Harness;
+use crossterm::event::KeyCode;
+use tuitype::cli::CliCommand;
+use tuitype::config::{Config, TestMode};
+use tuitype::profile::{DEFAULT_PROFILE, Profiles, validate_name};
+use tuitype::stats::TestHistory;
+use tuitype::storage::Storage;
+use tuitype::ui::{App, MenuState, ThemeType};
+
+#[test]
+fn profiles_keep_their_own_settings_and_history() {
+    let mut harness = Harness::custom("hi");
+    harness.app.load_profile(None).unwrap();
+    assert_eq!(harness.app.profile(), DEFAULT_PROFILE);
+
+    harness.press(KeyCode::Esc).type_text("3").type_text("3");
+    assert_eq!(harness.app.config.theme_type, ThemeType::Sepia);
+
+    harness.app.load_profile(Some("alice")).unwrap();
+    assert_eq!(harness.app.profile(), "alice");
+    assert_eq!(harness.app.config.theme_type, ThemeType::Dark);
+    assert!(harness.app.history.as_ref().unwrap().tests.is_empty());
+
+    harness.press(KeyCode::Esc).type_text("3").type_text("5");
+    harness.app.config.test_mode = TestMode::Custom;
+    harness.app.config.custom_text = Some("hi".to_string());
+    harness.app.restart_test();
+    harness.type_text("hi");
+    assert!(harness.app.session.test_complete);
+
+    let alice = harness.storage.get("profiles/alice/config.toml").unwrap();
+    assert!(alice.contains("theme_type = \"Ocean\""));
+    let alice_history = harness.storage.get("profiles/alice/history.json").unwrap();
+    assert!(alice_history.contains("\"hi\""));
+    assert!(harness.storage.get(TestHistory::STORAGE_KEY).is_none());
+
+    harness.app.load_profile(Some(DEFAULT_PROFILE)).unwrap();
+    assert_eq!(harness.app.config.theme_type, ThemeType::Sepia);
+    assert!(harness.app.history.as_ref().unwrap().tests.is_empty());
+}
+
+#[test]
+fn the_last_used_profile_is_loaded_next_time() {
+    let mut harness = Harness::custom("hi");
+    harness.app.load_profile(Some("Bob")).unwrap();
+    assert_eq!(harness.app.profile(), "bob");
+
+    let index = Profiles::load_from(&*harness.storage).unwrap();
+    assert_eq!(index.names(), vec![DEFAULT_PROFILE, "bob"]);
+    assert_eq!(index.last_used(), "bob");
+
+    let mut app = App::new(Config::default());
+    app.set_storage(harness.storage.clone());
+    app.load_profile(None).unwrap();
+    assert_eq!(app.profile(), "bob");
+}
+
+#[test]
+fn the_profile_list_is_only_saved_when_it_changes() {
+    let harness = Harness::custom("hi");
+    let unchanged = r#"{"names": ["bob"], "last_used": "bob"}"#;
+    harness
+        .storage
+        .write(Profiles::STORAGE_KEY, unchanged)
+        .unwrap();
+
+    let mut app = App::new(Config::default());
+    app.set_storage(harness.storage.clone());
+    app.load_profile(None).unwrap();
+    app.load_profile(Some("bob")).unwrap();
+    assert_eq!(
+        harness.storage.get(Profiles::STORAGE_KEY).as_deref(),
+        Some(unchanged)
+    );
+
+    app.load_profile(Some("carol")).unwrap();
+    let index = Profiles::load_from(&*harness.storage).unwrap();
+    assert_eq!(index.names(), vec![DEFAULT_PROFILE, "bob", "carol"]);
+    assert_eq!(index.last_used(), "carol");
+}
+
+#[test]
+fn profiles_are_created_and_switched_from_the_menu() {
+    let mut harness = Harness::custom("hi");
+    harness.app.load_profile(None).unwrap();
+
+    harness.press(KeyCode::Esc).type_text("7");
+    assert_eq!(harness.app.menu_state, MenuState::ProfileMenu(0));
+
+    harness.type_text("2");
+    assert!(matches!(
+        harness.app.menu_state,
+        MenuState::NewProfileInput { .. }
+    ));
+    harness.type_text("carol 2").press(KeyCode::Enter);
+    let MenuState::NewProfileInput { error, .. } = &harness.app.menu_state else {
+        panic!("expected the name prompt, got {:?}", harness.app.menu_state);
+    };
+    assert!(error.as_deref().unwrap().contains("letters, digits"));
+
+    for _ in 0..2 {
+        harness.press(KeyCode::Backspace);
+    }
+    harness.type_text("-2").press(KeyCode::Enter);
+    assert_eq!(harness.app.profile(), "carol-2");
+    assert_eq!(harness.app.menu_state, MenuState::Typing);
+
+    harness.press(KeyCode::Esc).type_text("7");
+    assert_eq!(harness.app.menu_state, MenuState::ProfileMenu(1));
+    assert!(harness.render(100, 30).contains("carol-2 (current)"));
+
+    harness.type_text("1");
+    assert_eq!(harness.app.profile(), DEFAULT_PROFILE);
+}
+
+#[test]
+fn profile_names_are_validated() {
+    assert_eq!(validate_name(" Dana ").unwrap(), "dana");
+    assert!(validate_name("").is_err());
+    assert!(validate_name("../etc").is_err());
+    assert!(validate_name(&"x".repeat(33)).is_err());
+}
+
+#[test]
+fn profile_flag_is_parsed() {
+    let args = ["--profile", "Erin"].map(String::from);
+    let CliCommand::Run(args) = CliCommand::parse(args).unwrap() else {
+        panic!("expected run");
+    };
+    assert_eq!(args.profile.as_deref(), Some("erin"));
+
+    assert!(CliCommand::parse(["--profile".to_string()]).is_err());
+    assert!(CliCommand::parse(["--profile", "a/b"].map(String::from)).is_err());
+}
+
+#[test]
+fn broken_history_is_reported_and_left_alone() {
+    let harness = Harness::custom("hi");
+    harness
+        .storage
+        .write("profiles/frank/history.json", "{ not json")
+        .unwrap();
+
+    let mut app = App::new(Config::default());
+    app.set_storage(harness.storage.clone());
+    let warnings = app.load_profile(Some("frank")).unwrap();
+    assert!(warnings[0].contains("History could not be loaded"));
+    assert!(app.history.is_none());
+    assert_eq!(
+        harness
+            .storage
+            .get("profiles/frank/history.json")
+            .as_deref(),
+        Some("{ not json")
+    );
+}
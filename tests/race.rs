@@ -0,0 +1,285 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::Harness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
+use tuitype::config::{Config, Difficulty, TestMode};
+use tuitype::net::{
+    ClientMessage, MAX_LINE, Race, RaceClient, RacePhase, RaceServer, RaceSettings, ServerMessage,
+};
+use tuitype::ui::MenuState;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn wait_until(mut done: impl FnMut() -> bool) {
+    let deadline = Instant::now() + TIMEOUT;
+    while !done() {
+        assert!(Instant::now() < deadline, "timed out waiting for the race");
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+fn recv(client: &mut RaceClient, mut matches: impl FnMut(&ServerMessage) -> bool) -> ServerMessage {
+    let mut found = None;
+    wait_until(|| {
+        while let Some(message) = client.try_recv() {
+            if matches(&message) {
+                found = Some(message);
+                return true;
+            }
+        }
+        false
+    });
+    found.unwrap()
+}
+
+fn settings(text: &str) -> RaceSettings {
+    RaceSettings {
+        seed: 42,
+        test_mode: TestMode::Custom,
+        difficulty: Difficulty::Medium,
+        custom_text: Some(text.to_string()),
+        word_list: None,
+    }
+}
+
+#[test]
+fn server_ranks_finished_players() {
+    let server = RaceServer::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let addr = server.local_addr();
+
+    let mut alice = RaceClient::connect(addr, "alice").unwrap();
+    let mut bob = RaceClient::connect(addr, "bob").unwrap();
+    wait_until(|| server.player_count() == 2);
+
+    server.start_race(settings("hello world"));
+    for client in [&mut alice, &mut bob] {
+        let ServerMessage::Start { race } =
+            recv(client, |m| matches!(m, ServerMessage::Start { .. }))
+        else {
+            unreachable!()
+        };
+        assert_eq!(race, settings("hello world"));
+    }
+
+    alice.send(&ClientMessage::Progress {
+        position: 5,
+        length: 11,
+        wpm: 40.0,
+    });
+    let ServerMessage::Players { players } = recv(&mut bob, |m| {
+        matches!(m, ServerMessage::Players { players }
+            if players.iter().any(|p| p.name == "alice" && p.position == 5))
+    }) else {
+        unreachable!()
+    };
+    assert_eq!(players.len(), 2);
+
+    bob.send(&ClientMessage::Finished {
+        wpm: 50.0,
+        accuracy: 100.0,
+        elapsed_ms: 3000,
+    });
+    alice.send(&ClientMessage::Finished {
+        wpm: 70.0,
+        accuracy: 95.0,
+        elapsed_ms: 2000,
+    });
+
+    let ServerMessage::Standings { players } =
+        recv(&mut alice, |m| matches!(m, ServerMessage::Standings { .. }))
+    else {
+        unreachable!()
+    };
+    let names: Vec<_> = players.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["alice", "bob"]);
+    assert!(!server.is_racing());
+}
+
+fn joined_pair(host: Harness, guest: Harness) -> (Harness, Harness) {
+    let (mut host, mut guest) = (host, guest);
+    host.app.set_race(Race::host(0, "host").unwrap());
+    let port = host
+        .app
+        .race
+        .as_ref()
+        .unwrap()
+        .address
+        .trim_start_matches("port ")
+        .parse::<u16>()
+        .unwrap();
+
+    guest
+        .app
+        .set_race(Race::join(SocketAddr::from((Ipv4Addr::LOCALHOST, port)), "guest").unwrap());
+
+    wait_until(|| {
+        host.advance(Duration::ZERO);
+        host.app.race.as_ref().unwrap().players.len() == 2
+    });
+    (host, guest)
+}
+
+fn start(host: &mut Harness, guest: &mut Harness) {
+    host.press(KeyCode::Enter);
+    wait_until(|| {
+        host.advance(Duration::ZERO);
+        guest.advance(Duration::ZERO);
+        guest.app.race.as_ref().unwrap().phase == RacePhase::Racing
+            && host.app.race.as_ref().unwrap().phase == RacePhase::Racing
+    });
+}
+
+#[test]
+fn players_race_the_same_text() {
+    let (mut host, mut guest) = joined_pair(
+        Harness::custom("warm up"),
+        Harness::custom("something else"),
+    );
+
+    host.app.config.custom_text = Some("go fast".to_string());
+    start(&mut host, &mut guest);
+
+    assert_eq!(host.app.menu_state, MenuState::Typing);
+    assert_eq!(guest.app.menu_state, MenuState::Typing);
+    assert_eq!(
+        host.app.session.text_source.full_text(),
+        guest.app.session.text_source.full_text()
+    );
+    assert_eq!(host.app.session.text_source.full_text(), "go fast");
+
+    host.type_text("go fast");
+    guest.type_text("go fast");
+    assert_eq!(host.app.menu_state, MenuState::TestComplete);
+
+    wait_until(|| {
+        host.advance(Duration::ZERO);
+        guest.advance(Duration::ZERO);
+        [&host, &guest]
+            .iter()
+            .all(|h| h.app.race.as_ref().unwrap().phase == RacePhase::Finished)
+    });
+
+    let standings = guest.app.race.as_ref().unwrap().standings();
+    assert_eq!(standings.len(), 2);
+    assert!(standings.iter().all(|player| player.result.is_some()));
+    assert!(guest.render(100, 30).contains("Race Results"));
+}
+
+#[test]
+fn joiners_use_the_host_word_list() {
+    let words = Config {
+        test_mode: TestMode::Words(10),
+        word_list: Some(vec!["rust".to_string(), "crab".to_string()]),
+        ..Config::default()
+    };
+    let (mut host, mut guest) = joined_pair(
+        Harness::new(words),
+        Harness::new(Config {
+            test_mode: TestMode::Words(10),
+            ..Config::default()
+        }),
+    );
+
+    start(&mut host, &mut guest);
+    let text = host.app.session.text_source.full_text();
+    assert_eq!(text, guest.app.session.text_source.full_text());
+    assert!(
+        text.split(' ').all(|word| word == "rust" || word == "crab"),
+        "{text}"
+    );
+    assert_eq!(guest.app.config.word_list, None);
+}
+
+#[test]
+fn menus_and_restarts_keep_the_race_text() {
+    let (mut host, mut guest) = joined_pair(Harness::custom("go fast"), Harness::custom("warm up"));
+    start(&mut host, &mut guest);
+    let text = host.app.session.text_source.full_text().to_string();
+
+    guest.type_text("go");
+    guest.keys(&[KeyCode::Esc, KeyCode::Down, KeyCode::Enter, KeyCode::Enter]);
+    guest
+        .press_with(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .type_text("restart test")
+        .press(KeyCode::Enter);
+    guest.app.load_profile(Some("alice")).unwrap();
+
+    assert_eq!(guest.app.menu_state, MenuState::Typing);
+    assert_eq!(guest.app.session.text_source.full_text(), text);
+    assert_eq!(guest.app.session.typed_text(), "go");
+}
+
+#[test]
+fn a_stalled_joiner_does_not_block_the_host() {
+    let server = RaceServer::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let mut stalled = TcpStream::connect(server.local_addr()).unwrap();
+    stalled
+        .write_all(b"{\"type\":\"join\",\"name\":\"stalled\"}\n")
+        .unwrap();
+    wait_until(|| server.player_count() == 1);
+
+    let text = "word ".repeat(100_000);
+    let started = Instant::now();
+    for _ in 0..50 {
+        server.start_race(settings(&text));
+    }
+    assert!(started.elapsed() < TIMEOUT);
+
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while server.player_count() != 0 {
+        assert!(
+            Instant::now() < deadline,
+            "the stalled joiner was never dropped"
+        );
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn a_stalled_host_does_not_block_the_client() {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let mut client = RaceClient::connect(listener.local_addr().unwrap(), "guest").unwrap();
+    let _stalled = listener.accept().unwrap();
+
+    let started = Instant::now();
+    for position in 0..500_000 {
+        client.send(&ClientMessage::Progress {
+            position,
+            length: 500_000,
+            wpm: 60.0,
+        });
+    }
+    assert!(started.elapsed() < TIMEOUT);
+
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while client.try_recv().is_some() || client.is_connected() {
+        assert!(
+            Instant::now() < deadline,
+            "the stalled host was never dropped"
+        );
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn oversized_messages_disconnect_the_sender() {
+    let server = RaceServer::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let mut client = TcpStream::connect(server.local_addr()).unwrap();
+    client.set_read_timeout(Some(TIMEOUT)).unwrap();
+
+    let name = "x".repeat(MAX_LINE as usize);
+    client
+        .write_all(format!("{{\"type\":\"join\",\"name\":\"{name}\"}}\n").as_bytes())
+        .ok();
+
+    let mut reply = Vec::new();
+    client.read_to_end(&mut reply).ok();
+    assert!(reply.is_empty());
+    assert_eq!(server.player_count(), 0);
+}
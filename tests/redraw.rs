@@ -0,0 +1,48 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::Harness;
+use crossterm::event::KeyCode;
+use std::time::Duration;
+use tuitype::input::Event;
+
+#[test]
+fn idle_ticks_do_not_redraw() {
+    let mut harness = Harness::custom("hello world");
+    assert!(harness.app.take_redraw());
+
+    harness.advance(Duration::from_secs(1));
+    assert!(!harness.app.take_redraw());
+
+    harness.press(KeyCode::Esc);
+    assert!(harness.app.take_redraw());
+    harness.advance(Duration::from_secs(1));
+    assert!(!harness.app.take_redraw());
+}
+
+#[test]
+fn running_test_redraws_until_complete() {
+    let mut harness = Harness::custom("hi");
+    harness.press(KeyCode::Char('h'));
+    harness.app.take_redraw();
+
+    harness.advance(Duration::from_millis(33));
+    assert!(harness.app.take_redraw());
+
+    harness.press(KeyCode::Char('i'));
+    assert!(harness.app.session.test_complete);
+    harness.app.take_redraw();
+
+    harness.advance(Duration::from_millis(33));
+    assert!(!harness.app.take_redraw());
+}
+
+#[test]
+fn resize_redraws() {
+    let mut harness = Harness::custom("hello world");
+    harness.app.take_redraw();
+
+    harness.app.handle_events(Event::Resize(80, 24)).unwrap();
+    assert!(harness.app.take_redraw());
+}
@@ -0,0 +1,268 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use chrono::TimeZone;
+use common::{HEIGHT, Harness, WIDTH};
+use crossterm::event::KeyCode;
+use std::time::Duration;
+use tuitype::config::{CaretStyle, Config, TestMode};
+use tuitype::stats::{Keystroke, KeystrokeKind, TestHistory, TestResult};
+
+const TEXT: &str = "the quick brown fox jumps over the lazy dog";
+
+fn history() -> TestHistory {
+    let keystrokes = "the quick"
+        .chars()
+        .enumerate()
+        .map(|(i, c)| Keystroke {
+            elapsed_ms: (i as u64 + 1) * 150,
+            key: KeystrokeKind::Char(c),
+            cursor: i + 1,
+        })
+        .collect();
+
+    let mut history = TestHistory::default();
+    history.add_result(TestResult {
+        timestamp: chrono::Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap(),
+        duration: 1.35,
+        wpm: 80.0,
+        accuracy: 100.0,
+        mode: "custom".to_string(),
+        text: "the quick".to_string(),
+        keystrokes,
+    });
+    history
+}
+
+#[test]
+fn typing_initial() {
+    Harness::custom(TEXT).assert_snapshot("typing_initial");
+}
+
+#[test]
+fn typing_in_progress() {
+    let mut harness = Harness::custom(TEXT);
+    harness.type_text("the quikc brown");
+    harness.assert_snapshot("typing_in_progress");
+}
+
+#[test]
+fn typing_bar_caret() {
+    let mut harness = Harness::custom(TEXT);
+    harness.app.config.caret_style = CaretStyle::Bar;
+    harness.type_text("the q");
+    let screen = harness.render(WIDTH, HEIGHT);
+    assert!(screen.contains("the q\u{258f}uick brown"), "{screen}");
+
+    harness.app.config.use_terminal_cursor = true;
+    assert!(!harness.render(WIDTH, HEIGHT).contains('\u{258f}'));
+}
+
+#[test]
+fn typing_word_skipped() {
+    let mut harness = Harness::custom(TEXT);
+    harness.type_text("the qu fox");
+    harness.assert_snapshot("typing_word_skipped");
+}
+
+#[test]
+fn typing_timed_mode() {
+    let config = Config {
+        test_mode: TestMode::Timed(30),
+        ..Config::default()
+    };
+    let mut harness = Harness::new(config);
+    let words: String = harness
+        .app
+        .session
+        .text_source
+        .full_text()
+        .chars()
+        .take(12)
+        .collect();
+    harness.type_text(&words);
+    harness.advance(Duration::from_secs(5));
+    harness.assert_snapshot("typing_timed_mode");
+}
+
+#[test]
+fn test_complete() {
+    let mut harness = Harness::custom(TEXT);
+    harness.type_text(TEXT);
+    harness.assert_snapshot("test_complete");
+}
+
+#[test]
+fn test_complete_with_seed() {
+    let mut harness = Harness::new(Config {
+        test_mode: TestMode::Words(5),
+        ..Config::default()
+    });
+    let text = harness.app.session.text_source.full_text().to_string();
+    harness.type_text(&text);
+    harness.assert_snapshot("test_complete_with_seed");
+}
+
+#[test]
+fn main_menu() {
+    let mut harness = Harness::custom(TEXT);
+    harness.press(KeyCode::Esc);
+    harness.assert_snapshot("main_menu");
+}
+
+#[test]
+fn profile_menu() {
+    let mut harness = Harness::custom(TEXT);
+    harness.app.load_profile(Some("alice")).unwrap();
+    harness.keys(&[KeyCode::Esc]).type_text("7");
+    harness.assert_snapshot("profile_menu");
+}
+
+#[test]
+fn test_mode_menu() {
+    let mut harness = Harness::custom(TEXT);
+    harness.keys(&[KeyCode::Esc, KeyCode::Enter]);
+    harness.assert_snapshot("test_mode_menu");
+}
+
+#[test]
+fn time_menu() {
+    let mut harness = Harness::custom(TEXT);
+    harness.keys(&[KeyCode::Esc, KeyCode::Enter, KeyCode::Enter]);
+    harness.assert_snapshot("time_menu");
+}
+
+#[test]
+fn custom_timed_input() {
+    let mut harness = Harness::custom(TEXT);
+    harness.keys(&[KeyCode::Esc, KeyCode::Enter, KeyCode::Enter]);
+    harness.keys(&[KeyCode::Down; 4]);
+    harness.press(KeyCode::Enter);
+    harness.type_text("45");
+    harness.assert_snapshot("custom_timed_input");
+}
+
+#[test]
+fn word_count_menu() {
+    let mut harness = Harness::custom(TEXT);
+    harness.keys(&[KeyCode::Esc, KeyCode::Enter, KeyCode::Down, KeyCode::Enter]);
+    harness.assert_snapshot("word_count_menu");
+}
+
+#[test]
+fn custom_words_input() {
+    let mut harness = Harness::custom(TEXT);
+    harness.keys(&[KeyCode::Esc, KeyCode::Enter, KeyCode::Down, KeyCode::Enter]);
+    harness.keys(&[KeyCode::Down; 3]);
+    harness.press(KeyCode::Enter);
+    harness.type_text("75");
+    harness.assert_snapshot("custom_words_input");
+}
+
+#[test]
+fn difficulty_menu() {
+    let mut harness = Harness::custom(TEXT);
+    harness.keys(&[KeyCode::Esc, KeyCode::Down, KeyCode::Enter]);
+    harness.assert_snapshot("difficulty_menu");
+}
+
+#[test]
+fn theme_menu() {
+    let mut harness = Harness::custom(TEXT);
+    harness.keys(&[KeyCode::Esc, KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+    harness.assert_snapshot("theme_menu");
+}
+
+#[test]
+fn settings_menu() {
+    let mut harness = Harness::custom(TEXT);
+    harness.keys(&[KeyCode::Esc, KeyCode::Down, KeyCode::Down, KeyCode::Down]);
+    harness.keys(&[KeyCode::Enter, KeyCode::Down]);
+    harness.assert_snapshot("settings_menu");
+}
+
+#[test]
+fn custom_pace_input() {
+    let mut harness = Harness::custom(TEXT);
+    harness.keys(&[KeyCode::Esc, KeyCode::Down, KeyCode::Down, KeyCode::Down]);
+    harness.press(KeyCode::Enter);
+    harness.keys(&[KeyCode::Down; 6]);
+    harness.press(KeyCode::Enter);
+    harness.type_text("90");
+    harness.assert_snapshot("custom_pace_input");
+}
+
+#[test]
+fn history_menu_empty() {
+    let mut harness = Harness::custom(TEXT);
+    harness.press(KeyCode::Esc);
+    harness.keys(&[KeyCode::Down; 4]);
+    harness.press(KeyCode::Enter);
+    harness.assert_snapshot("history_menu_empty");
+}
+
+#[test]
+fn history_menu() {
+    let mut harness = Harness::custom(TEXT);
+    harness.app.set_history(history());
+    harness.press(KeyCode::Esc);
+    harness.keys(&[KeyCode::Down; 4]);
+    harness.press(KeyCode::Enter);
+    harness.assert_snapshot("history_menu");
+}
+
+#[test]
+fn replay() {
+    let mut harness = Harness::custom(TEXT);
+    harness.app.set_history(history());
+    harness.press(KeyCode::Esc);
+    harness.keys(&[KeyCode::Down; 4]);
+    harness.keys(&[KeyCode::Enter, KeyCode::Enter]);
+    harness.advance(Duration::from_millis(600));
+    harness.assert_snapshot("replay");
+}
+
+#[test]
+fn help() {
+    let mut harness = Harness::custom(TEXT);
+    harness.press(KeyCode::Esc);
+    harness.keys(&[KeyCode::Down; 5]);
+    harness.press(KeyCode::Enter);
+    harness.assert_snapshot("help");
+}
+
+#[test]
+fn command_palette() {
+    let mut harness = Harness::custom(TEXT);
+    harness.type_text(":them").press(KeyCode::Down);
+    harness.assert_snapshot("command_palette");
+}
+
+#[test]
+fn repeat_mode_warning() {
+    let config = Config {
+        test_mode: TestMode::Custom,
+        custom_text: Some(TEXT.to_string()),
+        repeat_test: true,
+        ..Config::default()
+    };
+    let mut harness = Harness::new(config);
+    harness.keys(&[KeyCode::Esc, KeyCode::Down, KeyCode::Enter, KeyCode::Enter]);
+    harness.assert_snapshot("repeat_mode_warning");
+}
+
+#[test]
+fn config_recovered_warning() {
+    let mut harness = Harness::custom(TEXT);
+    harness.app.show_config_warnings(vec![
+        "Ignored invalid settings: theme_type. Their defaults are in use.".to_string(),
+        "The original file was saved as config.json.20240501-120000.bak.".to_string(),
+    ]);
+    harness.assert_snapshot("config_recovered_warning");
+}
+
+#[test]
+fn terminal_too_small() {
+    Harness::custom(TEXT).assert_snapshot_sized("terminal_too_small", 60, 15);
+}
@@ -0,0 +1,77 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::sync::Arc;
+use std::time::Duration;
+use tuitype::cli::CliCommand;
+use tuitype::clock::ManualClock;
+use tuitype::input::{Event, InputHandler, Script};
+
+const SCRIPT: &str = "\
+# type a word, fix a typo, open the menu
+t=0ms key=h
+t=120ms key=x
+t=200ms key=backspace
+t=250ms key=i
+t=1.5s key=space
+t=2s key=ctrl+c
+";
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::empty())
+}
+
+#[test]
+fn parses_timed_keys() {
+    let script = Script::parse(SCRIPT).unwrap();
+    assert_eq!(script.len(), 6);
+}
+
+#[test]
+fn rejects_bad_lines() {
+    let error = Script::parse("t=10ms key=a\nt=5ms key=b").unwrap_err();
+    assert!(format!("{error:#}").contains("line 2"));
+
+    assert!(Script::parse("t=10 key=a").is_err());
+    assert!(Script::parse("t=10ms key=f13").is_err());
+    assert!(Script::parse("key=a").is_err());
+}
+
+#[test]
+fn releases_keys_when_due() {
+    let clock = Arc::new(ManualClock::new());
+    let script = Script::parse(SCRIPT).unwrap();
+    let mut input = InputHandler::from_script(Duration::from_secs(3600), script, clock.clone());
+
+    let mut next = |at_ms: u64| {
+        clock.set(Duration::from_millis(at_ms));
+        match input.poll_event().unwrap() {
+            Some(Event::Key(key)) => Some(key),
+            _ => None,
+        }
+    };
+
+    assert_eq!(next(0), Some(key(KeyCode::Char('h'))));
+    assert_eq!(next(150), Some(key(KeyCode::Char('x'))));
+    assert_eq!(next(260), Some(key(KeyCode::Backspace)));
+    assert_eq!(next(260), Some(key(KeyCode::Char('i'))));
+    assert_eq!(next(1500), Some(key(KeyCode::Char(' '))));
+    assert_eq!(
+        next(2000),
+        Some(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL))
+    );
+    assert!(input.script_finished());
+}
+
+#[test]
+fn parses_cli_script_flags() {
+    let args = ["--script", "demo.keys", "--exit-after-script"].map(String::from);
+    let CliCommand::Run(args) = CliCommand::parse(args).unwrap() else {
+        panic!("expected run command");
+    };
+    assert_eq!(args.script.as_deref(), Some("demo.keys".as_ref()));
+    assert!(args.exit_after_script);
+
+    assert!(CliCommand::parse(["--exit-after-script".to_string()]).is_err());
+    assert!(CliCommand::parse(["--bogus".to_string()]).is_err());
+}
@@ -0,0 +1,89 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::Harness;
+use crossterm::event::KeyCode;
+use tuitype::TextSource;
+use tuitype::config::{Config, Difficulty, TestMode};
+
+fn config(test_mode: TestMode, seed: Option<u64>) -> Config {
+    Config {
+        test_mode,
+        difficulty: Difficulty::Hard,
+        seed,
+        ..Config::default()
+    }
+}
+
+#[test]
+fn same_seed_gives_same_text() {
+    for mode in [TestMode::Words(25), TestMode::Timed(30), TestMode::Quote] {
+        let a = TextSource::new(&config(mode, Some(7)));
+        let b = TextSource::new(&config(mode, Some(7)));
+        assert_eq!(a.full_text(), b.full_text());
+        assert_eq!(a.seed(), 7);
+    }
+
+    let a = TextSource::new(&config(TestMode::Words(25), Some(7)));
+    let b = TextSource::new(&config(TestMode::Words(25), Some(8)));
+    assert_ne!(a.full_text(), b.full_text());
+}
+
+#[test]
+fn seeded_timed_text_extends_identically() {
+    let mut a = TextSource::new(&config(TestMode::Timed(60), Some(99)));
+    let mut b = TextSource::new(&config(TestMode::Timed(60), Some(99)));
+    a.add_more_words();
+    b.add_more_words();
+    assert_eq!(a.full_text(), b.full_text());
+}
+
+#[test]
+fn unseeded_tests_still_report_a_seed() {
+    let source = TextSource::new(&config(TestMode::Words(10), None));
+    let replayed = TextSource::new(&config(TestMode::Words(10), Some(source.seed())));
+    assert_eq!(source.full_text(), replayed.full_text());
+}
+
+#[test]
+fn retry_seed_restarts_with_identical_text() {
+    let mut harness = Harness::new(config(TestMode::Words(3), None));
+    harness.app.set_seed(None);
+    let first = harness.app.session.text_source.full_text().to_string();
+    harness.type_text(&first);
+    assert!(harness.app.session.test_complete);
+
+    harness.press(KeyCode::Char('r'));
+    assert!(!harness.app.session.test_complete);
+    assert_eq!(harness.app.session.text_source.full_text(), first);
+}
+
+#[test]
+fn seeded_text_is_stable_across_builds() {
+    let source = TextSource::new(&config(TestMode::Words(5), Some(7)));
+    assert_eq!(
+        source.full_text(),
+        "disenfranchise egregious hierarchical redundancy commensurate"
+    );
+}
+
+#[test]
+fn repeated_text_does_not_offer_a_seed_retry() {
+    let mut harness = Harness::new(Config {
+        repeat_test: true,
+        ..config(TestMode::Words(3), Some(7))
+    });
+    let text = harness.app.session.text_source.full_text().to_string();
+    harness.type_text(&text);
+    assert!(harness.render(100, 30).contains("retry this seed"));
+
+    harness.app.restart_test();
+    assert!(harness.app.config.last_test_text.is_some());
+    harness.type_text(&text);
+    assert!(harness.app.session.test_complete);
+
+    let screen = harness.render(100, 30);
+    assert!(!screen.contains("retry this seed"), "{screen}");
+    assert!(!screen.contains("Seed:"), "{screen}");
+}
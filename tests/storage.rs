@@ -0,0 +1,237 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::Harness;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tuitype::cli::CliCommand;
+use tuitype::clock::ManualClock;
+use tuitype::config::{Config, TestMode};
+use tuitype::profile::Profiles;
+use tuitype::stats::{Keystroke, KeystrokeKind, TestHistory, TestResult};
+use tuitype::storage::{FileStorage, MemoryStorage, ReadOnlyStorage, Storage};
+use tuitype::ui::{App, ThemeType};
+
+#[test]
+fn config_round_trips_through_storage() {
+    let storage = MemoryStorage::new();
+
+    let loaded = Config::load_from(&storage);
+    assert_eq!(loaded.config, Config::default());
+    assert!(loaded.warnings.is_empty());
+    assert!(storage.get(Config::STORAGE_KEY).is_some());
+
+    let config = Config {
+        theme_type: ThemeType::Sepia,
+        test_mode: TestMode::Words(25),
+        ..Config::default()
+    };
+    config.save_to(&storage).unwrap();
+    assert_eq!(Config::load_from(&storage).config, config);
+}
+
+#[test]
+fn missing_history_loads_empty() {
+    let storage = MemoryStorage::new();
+    let history = TestHistory::load_from(&storage).unwrap();
+    assert!(history.tests.is_empty());
+    assert!(storage.get(TestHistory::STORAGE_KEY).is_none());
+}
+
+#[test]
+fn file_storage_creates_its_directory() {
+    let dir = std::env::temp_dir().join(format!("tuitype-storage-{}", std::process::id()));
+    let storage = FileStorage::new(dir.join("nested"));
+
+    assert_eq!(storage.read("config.json").unwrap(), None);
+    storage.write("config.json", "{}").unwrap();
+    assert_eq!(storage.read("config.json").unwrap().as_deref(), Some("{}"));
+    assert!(storage.dir().join("config.json").is_file());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn app_saves_settings_and_history_to_its_storage() {
+    let mut harness = Harness::custom("hi");
+    harness.app.set_history(TestHistory::default());
+
+    harness.press(KeyCode::Esc).type_text("3").type_text("3");
+    assert_eq!(harness.app.config.theme_type, ThemeType::Sepia);
+    let saved = Config::load_from(&*harness.storage).config;
+    assert_eq!(saved.theme_type, ThemeType::Sepia);
+
+    harness.type_text("hi");
+    assert!(harness.app.session.test_complete);
+    let history = TestHistory::load_from(&*harness.storage).unwrap();
+    assert_eq!(history.tests.len(), 1);
+    assert_eq!(history.tests[0].text, "hi");
+}
+
+#[test]
+fn results_are_stamped_with_the_app_clock() {
+    let start = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    let clock = Arc::new(ManualClock::starting_at(start));
+    let mut app = App::with_clock(
+        Config {
+            test_mode: TestMode::Custom,
+            custom_text: Some("hi".to_string()),
+            ..Config::default()
+        },
+        clock.clone(),
+    );
+    app.set_storage(Arc::new(MemoryStorage::new()));
+    app.set_history(TestHistory::default());
+
+    clock.advance(Duration::from_secs(90));
+    for c in "hi".chars() {
+        app.handle_key_event(KeyEvent::from(KeyCode::Char(c)))
+            .unwrap();
+        app.tick().unwrap();
+    }
+
+    let history = app.history.as_ref().unwrap();
+    assert_eq!(history.tests[0].timestamp, start + Duration::from_secs(90));
+}
+
+#[test]
+fn config_dir_can_be_overridden_by_the_environment() {
+    let resolve = |vars: &[(&str, &str)]| {
+        FileStorage::resolve_dir(|name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| OsString::from(value))
+        })
+    };
+
+    assert_eq!(
+        resolve(&[
+            (FileStorage::DIR_ENV, "/srv/tuitype"),
+            ("XDG_CONFIG_HOME", "/xdg")
+        ]),
+        PathBuf::from("/srv/tuitype")
+    );
+    assert_eq!(
+        resolve(&[("XDG_CONFIG_HOME", "/xdg")]),
+        PathBuf::from("/xdg/tuitype")
+    );
+    assert_eq!(
+        resolve(&[(FileStorage::DIR_ENV, ""), ("XDG_CONFIG_HOME", "relative")]),
+        resolve(&[])
+    );
+    assert!(resolve(&[]).ends_with("tuitype"));
+}
+
+#[test]
+fn config_and_read_only_flags_are_parsed() {
+    let args = ["--config", "ci/settings", "--read-only"].map(String::from);
+    let CliCommand::Run(args) = CliCommand::parse(args).unwrap() else {
+        panic!("expected run");
+    };
+    assert_eq!(args.config_dir, Some(PathBuf::from("ci/settings")));
+    assert!(args.read_only);
+
+    assert!(CliCommand::parse(["--config".to_string()]).is_err());
+}
+
+#[test]
+fn read_only_storage_never_writes_back() {
+    let dir = std::env::temp_dir().join(format!("tuitype-read-only-{}", std::process::id()));
+    let args = ["--config", dir.to_str().unwrap(), "--read-only"].map(String::from);
+    let CliCommand::Run(args) = CliCommand::parse(args).unwrap() else {
+        panic!("expected run");
+    };
+
+    let mut app = App::new(Config::default());
+    app.set_storage(args.storage());
+    let warnings = app.load_profile(Some("ci")).unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+    app.save_config().unwrap();
+    assert!(!dir.exists());
+}
+
+#[test]
+fn read_only_storage_keeps_changes_for_the_session() {
+    let disk = Arc::new(MemoryStorage::new());
+    disk.write(Config::STORAGE_KEY, "theme_type = \"Sepia\"\n")
+        .unwrap();
+
+    let mut harness = Harness::custom("hi");
+    harness
+        .app
+        .set_storage(Arc::new(ReadOnlyStorage::new(disk.clone())));
+    harness.app.load_profile(None).unwrap();
+    assert_eq!(harness.app.config.theme_type, ThemeType::Sepia);
+
+    harness.press(KeyCode::Esc).type_text("3").type_text("5");
+    harness.app.load_profile(Some("guest")).unwrap();
+    harness.app.load_profile(Some("default")).unwrap();
+    assert_eq!(harness.app.config.theme_type, ThemeType::Ocean);
+
+    assert_eq!(
+        disk.get(Config::STORAGE_KEY).as_deref(),
+        Some("theme_type = \"Sepia\"\n")
+    );
+    assert!(disk.get(Profiles::STORAGE_KEY).is_none());
+}
+
+#[test]
+fn history_is_capped_and_keeps_only_recent_recordings() {
+    let result = |wpm: f64, mode: &str| TestResult {
+        timestamp: chrono::Utc::now(),
+        duration: 10.0,
+        wpm,
+        accuracy: 100.0,
+        mode: mode.to_string(),
+        text: "hi".to_string(),
+        keystrokes: vec![Keystroke {
+            elapsed_ms: 100,
+            key: KeystrokeKind::Char('h'),
+            cursor: 1,
+        }],
+    };
+
+    let mut history = TestHistory::default();
+    history.add_result(result(200.0, "Quote"));
+    history.add_result(result(90.0, "Custom"));
+    for _ in 1..TestHistory::MAX_RESULTS {
+        history.add_result(result(50.0, "Custom"));
+    }
+
+    assert_eq!(history.tests.len(), TestHistory::MAX_RESULTS);
+    assert_eq!(history.total_tests, TestHistory::MAX_RESULTS + 1);
+    assert_eq!(history.best_wpm, 200.0);
+    assert!((history.average_wpm - 50.19).abs() < 0.01);
+
+    let recorded = history
+        .tests
+        .iter()
+        .filter(|test| !test.keystrokes.is_empty())
+        .count();
+    assert_eq!(recorded, TestHistory::MAX_RECORDINGS + 1);
+    assert_eq!(history.tests[0].wpm, 90.0);
+    assert!(!history.tests[0].keystrokes.is_empty());
+    assert!(history.tests[1].text.is_empty());
+    assert!(history.tests[1].keystrokes.is_empty());
+}
+
+#[test]
+fn notices_are_shown_without_blocking_input() {
+    let mut harness = Harness::custom("hi there");
+    harness
+        .app
+        .show_notice("Browser storage is unavailable, so settings and results won't be saved.");
+    assert!(
+        harness
+            .render(100, 30)
+            .contains("Browser storage is unavailable")
+    );
+
+    harness.type_text("hi");
+    assert_eq!(harness.app.session.typed_text(), "hi");
+}
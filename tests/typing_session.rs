@@ -0,0 +1,179 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use common::{Harness, SEED};
+use crossterm::event::KeyCode;
+use std::sync::Arc;
+use std::time::Duration;
+use tuitype::clock::{Clock, ManualClock};
+use tuitype::config::{BackspaceMode, Config, TestMode};
+use tuitype::session::TypingSession;
+
+fn recount(target: &[char], typed: &[char]) -> (usize, usize) {
+    let correct = typed
+        .iter()
+        .enumerate()
+        .filter(|&(i, c)| target.get(i) == Some(c))
+        .count();
+    (correct, typed.len() - correct)
+}
+
+#[test]
+fn incremental_stats_match_a_full_recount() {
+    let mut harness = Harness::custom("naïve café über straße");
+    harness
+        .type_text("naïve")
+        .press(KeyCode::Char(' '))
+        .type_text("cafe")
+        .keys(&[KeyCode::Backspace, KeyCode::Backspace])
+        .type_text("fé ü")
+        .press(KeyCode::Char(' '))
+        .type_text("strasse");
+
+    let session = &harness.app.session;
+    let (correct, incorrect) = recount(session.text_source.chars(), &session.typed_chars);
+    assert_eq!(session.stats.correct_chars, correct);
+    assert_eq!(session.stats.incorrect_chars, incorrect);
+    assert_eq!(session.stats.total_chars, session.typed_chars.len());
+    assert_eq!(session.cursor_pos, session.typed_chars.len());
+}
+
+#[test]
+fn long_custom_text_completes_on_the_last_char() {
+    let text = "lorem ipsum dolor sit amet ".repeat(400);
+    let text = text.trim_end();
+
+    let mut harness = Harness::custom(text);
+    harness.type_text(text);
+
+    let session = &harness.app.session;
+    assert!(session.test_complete);
+    assert_eq!(session.stats.correct_chars, text.chars().count());
+    assert_eq!(session.stats.accuracy, 100.0);
+}
+
+fn session(config: Config) -> (TypingSession, Arc<ManualClock>) {
+    let clock = Arc::new(ManualClock::new());
+    let config = Config {
+        seed: Some(SEED),
+        ..config
+    };
+    (TypingSession::with_clock(&config, clock.clone()), clock)
+}
+
+fn custom(text: &str) -> Config {
+    Config {
+        test_mode: TestMode::Custom,
+        custom_text: Some(text.to_string()),
+        ..Config::default()
+    }
+}
+
+#[test]
+fn timed_session_ends_when_tick_reaches_the_limit() {
+    let (mut session, clock) = session(Config {
+        test_mode: TestMode::Timed(15),
+        ..Config::default()
+    });
+
+    session.tick(clock.now());
+    assert!(!session.snapshot().started);
+
+    session.type_char(session.text_source.char_at(0).unwrap());
+    clock.advance(Duration::from_millis(14_500));
+    session.tick(clock.now());
+    assert_eq!(session.time_remaining, Some(1));
+    assert!(!session.test_complete);
+
+    clock.advance(Duration::from_millis(500));
+    session.tick(clock.now());
+    let snapshot = session.snapshot();
+    assert!(snapshot.complete);
+    assert_eq!(snapshot.time_remaining, Some(0));
+    assert_eq!(snapshot.elapsed_seconds, 15.0);
+
+    session.type_char('x');
+    assert_eq!(session.typed_chars.len(), 1);
+}
+
+#[test]
+fn end_on_first_error_stops_at_the_first_mistake() {
+    let (mut session, clock) = session(Config {
+        end_on_first_error: true,
+        ..custom("fox jumps")
+    });
+
+    for c in "fox".chars() {
+        clock.advance(Duration::from_millis(100));
+        session.type_char(c);
+    }
+    session.skip_word();
+    assert!(!session.test_complete);
+
+    clock.advance(Duration::from_millis(100));
+    session.type_char('k');
+    let snapshot = session.snapshot();
+    assert!(snapshot.complete);
+    assert_eq!(snapshot.typed_text, "fox k");
+    assert_eq!(snapshot.incorrect_chars, 1);
+    assert_eq!(
+        snapshot.end_reason.as_deref(),
+        Some("Test ended due to typing error")
+    );
+    assert!((snapshot.elapsed_seconds - 0.3).abs() < 1e-9);
+}
+
+#[test]
+fn scrollable_word_test_completes_after_the_last_word() {
+    let (mut session, clock) = session(Config {
+        test_mode: TestMode::Words(60),
+        word_list: Some(vec!["ab".to_string()]),
+        ..Config::default()
+    });
+    assert!(session.text_source.is_scrollable);
+
+    while !session.test_complete {
+        assert!(session.typed_chars.len() < 200, "test never completed");
+        clock.advance(Duration::from_millis(100));
+        match session.text_source.char_at(session.cursor_pos) {
+            Some(c) => session.type_char(c),
+            None => session.skip_word(),
+        }
+    }
+
+    let snapshot = session.snapshot();
+    assert_eq!(snapshot.typed_text, vec!["ab"; 60].join(" "));
+    assert_eq!(snapshot.accuracy, 100.0);
+    assert_eq!(session.text_source.total_words(), 60);
+}
+
+#[test]
+fn confidence_mode_only_deletes_into_mistyped_words() {
+    let (mut session, _) = session(Config {
+        backspace_mode: BackspaceMode::Confidence,
+        ..custom("one two three four")
+    });
+    assert!(!session.can_delete());
+
+    for c in "one tw".chars() {
+        session.type_char(c);
+    }
+    assert!(session.can_delete());
+    session.backspace();
+    assert_eq!(session.typed_text(), "one t");
+
+    session.type_char('w');
+    session.type_char('o');
+    session.skip_word();
+    assert!(!session.can_delete());
+    session.backspace();
+    assert_eq!(session.typed_text(), "one two ");
+
+    session.type_char('x');
+    session.skip_word();
+    assert!(session.can_delete());
+    session.backspace();
+    assert_eq!(session.typed_text(), "one two xxxxx");
+    assert_eq!(session.snapshot().correct_chars, 8);
+}
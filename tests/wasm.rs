@@ -0,0 +1,216 @@
+#![cfg(target_arch = "wasm32")]
+
+use std::sync::Arc;
+use tuitype::input::keys::{KeyCode, KeyModifiers};
+use tuitype::stats::TestHistory;
+use tuitype::storage::MemoryStorage;
+use tuitype::wasm::{WasmApp, key_event};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn in_memory() -> WasmApp {
+    WasmApp::with_storage(Arc::new(MemoryStorage::new()))
+}
+
+fn custom(text: &str) -> WasmApp {
+    let mut app = in_memory();
+    app.set_custom_text(text);
+    app
+}
+
+#[wasm_bindgen_test]
+fn dom_keys_map_to_key_events() {
+    assert_eq!(
+        key_event("a", false, false, false).unwrap().code,
+        KeyCode::Char('a')
+    );
+    assert_eq!(
+        key_event("ArrowUp", false, false, false).unwrap().code,
+        KeyCode::Up
+    );
+    assert_eq!(
+        key_event("Escape", false, false, false).unwrap().code,
+        KeyCode::Esc
+    );
+    assert_eq!(
+        key_event("F5", false, false, false).unwrap().code,
+        KeyCode::F(5)
+    );
+    assert_eq!(
+        key_event("Tab", false, false, true).unwrap().code,
+        KeyCode::BackTab
+    );
+
+    let ctrl_w = key_event("w", true, false, false).unwrap();
+    assert_eq!(ctrl_w.modifiers, KeyModifiers::CONTROL);
+
+    assert!(key_event("Shift", false, false, true).is_none());
+    assert!(key_event("Dead", false, false, false).is_none());
+}
+
+#[wasm_bindgen_test]
+fn typing_updates_the_session() {
+    let mut app = custom("hello world");
+    assert_eq!(app.text(), "hello world");
+    assert!(!app.is_started());
+
+    app.type_text("hellp").unwrap();
+    assert!(app.is_started());
+    assert_eq!(app.typed_text(), "hellp");
+    assert_eq!(app.cursor_position(), 5);
+    assert!(app.accuracy() < 100.0);
+
+    assert!(app.key_press("Backspace").unwrap());
+    assert_eq!(app.typed_text(), "hell");
+    assert!(!app.key_press("Shift").unwrap());
+}
+
+#[wasm_bindgen_test]
+fn completing_the_text_shows_results() {
+    let mut app = custom("hi there");
+    app.type_text("hi there").unwrap();
+
+    assert!(app.is_complete());
+    assert_eq!(app.screen(), "results");
+
+    let snapshot: serde_json::Value = serde_json::from_str(&app.snapshot()).unwrap();
+    assert_eq!(snapshot["complete"], true);
+    assert_eq!(snapshot["typed_text"], "hi there");
+
+    app.restart();
+    assert_eq!(app.screen(), "typing");
+    assert_eq!(app.typed_text(), "");
+}
+
+#[wasm_bindgen_test]
+fn keys_go_through_the_app_bindings() {
+    let mut app = custom("alpha beta");
+    app.key_press("Escape").unwrap();
+    assert_eq!(app.screen(), "menu");
+
+    app.key_press("Escape").unwrap();
+    assert_eq!(app.screen(), "typing");
+
+    app.key_down("c", true, false, false).unwrap();
+    assert!(!app.is_running());
+}
+
+#[wasm_bindgen_test]
+fn default_app_types_even_without_browser_storage() {
+    let mut app = WasmApp::new();
+    app.set_custom_text("hi there");
+    app.type_text("hi").unwrap();
+    assert_eq!(app.screen(), "typing");
+    assert_eq!(app.typed_text(), "hi");
+}
+
+#[wasm_bindgen_test]
+fn seeded_tests_can_be_retried() {
+    let mut app = in_memory();
+    app.set_test_mode("words", Some(5)).unwrap();
+    app.set_seed(Some(42));
+    let text = app.text();
+    assert_eq!(app.seed(), 42);
+
+    app.type_text("abc").unwrap();
+    app.retry_seed();
+    assert_eq!(app.text(), text);
+    assert_eq!(app.typed_text(), "");
+
+    assert!(app.set_test_mode("marathon", None).is_err());
+}
+
+#[wasm_bindgen_test]
+fn themes_are_exposed_as_rgb_triples() {
+    let mut app = in_memory();
+    app.set_theme("matrix").unwrap();
+    let colors = app.theme_colors();
+    assert_eq!(colors.len(), 21);
+
+    assert!(app.set_theme("neon").is_err());
+}
+
+fn screen_text(app: &WasmApp) -> Vec<String> {
+    let frame: serde_json::Value = serde_json::from_str(&app.render(100, 30).unwrap()).unwrap();
+    assert_eq!(frame["width"], 100);
+    assert_eq!(frame["height"], 30);
+
+    let cells = frame["cells"].as_array().unwrap();
+    assert_eq!(cells.len(), 100 * 30);
+    cells
+        .chunks(100)
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell["symbol"].as_str().unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn renders_the_tui_into_cells_and_takes_clicks() {
+    let mut app = custom("alpha beta");
+    assert!(
+        screen_text(&app)
+            .iter()
+            .any(|row| row.contains("alpha beta"))
+    );
+
+    app.key_press("Escape").unwrap();
+    let rows = screen_text(&app);
+    let (row, column) = rows
+        .iter()
+        .enumerate()
+        .find_map(|(y, line)| {
+            line.find("3. Theme")
+                .map(|x| (y, line[..x].chars().count()))
+        })
+        .unwrap();
+
+    app.click(column as u16, row as u16).unwrap();
+    assert!(screen_text(&app).iter().any(|row| row.contains("Ocean")));
+}
+
+#[wasm_bindgen_test]
+fn settings_and_history_survive_a_reload() {
+    let storage = Arc::new(MemoryStorage::new());
+
+    let mut app = WasmApp::with_storage(storage.clone());
+    app.set_theme("ocean").unwrap();
+    app.set_custom_text("go");
+    app.type_text("go").unwrap();
+    assert!(app.is_complete());
+    drop(app);
+
+    let history = TestHistory::load_from(&*storage).unwrap();
+    assert_eq!(history.tests.len(), 1);
+
+    let app = WasmApp::with_storage(storage);
+    assert_eq!(app.text(), "go");
+    assert_eq!(app.theme_colors(), {
+        let mut ocean = WasmApp::with_storage(Arc::new(MemoryStorage::new()));
+        ocean.set_theme("ocean").unwrap();
+        ocean.theme_colors()
+    });
+}
+
+#[wasm_bindgen_test]
+fn profiles_switch_settings_and_are_remembered() {
+    let storage = Arc::new(MemoryStorage::new());
+
+    let mut app = WasmApp::with_storage(storage.clone());
+    assert_eq!(app.profile(), "default");
+    app.set_custom_text("home");
+
+    app.set_profile("Guest").unwrap();
+    assert_eq!(app.profile(), "guest");
+    assert_ne!(app.text(), "home");
+    app.set_custom_text("away");
+    assert!(app.set_profile("no spaces").is_err());
+    drop(app);
+
+    let mut app = WasmApp::with_storage(storage);
+    assert_eq!(app.profile(), "guest");
+    assert_eq!(app.text(), "away");
+    app.set_profile("default").unwrap();
+    assert_eq!(app.text(), "home");
+}